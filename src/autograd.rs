@@ -0,0 +1,320 @@
+use std::cell::RefCell;
+use std::ops::{Add, AddAssign, BitAnd, Mul};
+use num_traits::Float;
+use crate::{nn, Matrix, MatrixError};
+
+
+/*
+Reverse-mode automatic differentiation
+A Tape records the operations applied to its variables, the value of every intermediate result is kept
+so backward can go through the tape from the end and propagate the gradients with the chain rule
+
+let tape = Tape::new();
+let w = tape.variable(weights);
+let x = tape.variable(inputs);
+let loss = (x * w).map(Function::Square).sum_line();
+let gradients = loss.backward();
+gradients.get(w)                    dloss / dw, same shape as w
+ */
+pub struct Tape<T> {
+    nodes : RefCell<Vec<Node<T>>>,
+}
+
+struct Node<T> {
+    value : Matrix<T>,
+    operation : Operation<T>,
+}
+
+/*
+Operation which produced a node, the usize are the indices of the operands in the tape
+ */
+enum Operation<T> {
+    Leaf,
+    Mul(usize, usize),
+    Add(usize, usize),
+    Hadamard(usize, usize),
+    AddToLines(usize, usize),
+    SumLine(usize),
+    Map(usize, Function<T>),
+    Transpose(usize),
+}
+
+/*
+Elementwise functions whose derivative is known
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function<T> {
+    Sigmoid,
+    Tanh,
+    Relu,
+    LeakyRelu(T), //slope for the negative inputs
+    Gelu,
+    Exp,
+    Ln,
+    Square,
+}
+
+impl<T> Function<T> where T : Float {
+    fn apply(&self, x : &Matrix<T>) -> Matrix<T> {
+        match *self {
+            Function::Sigmoid => nn::sigmoid(x),
+            Function::Tanh => nn::tanh(x),
+            Function::Relu => nn::relu(x),
+            Function::LeakyRelu(alpha) => nn::leaky_relu(x, alpha),
+            Function::Gelu => nn::gelu(x),
+            Function::Exp => x.clone().map(T::exp),
+            Function::Ln => x.clone().map(T::ln),
+            Function::Square => x.clone().map(|x| x * x),
+        }
+    }
+
+    fn derivative(&self, x : &Matrix<T>) -> Matrix<T> {
+        match *self {
+            Function::Sigmoid => nn::sigmoid_derivative(x),
+            Function::Tanh => nn::tanh_derivative(x),
+            Function::Relu => nn::relu_derivative(x),
+            Function::LeakyRelu(alpha) => nn::leaky_relu_derivative(x, alpha),
+            Function::Gelu => nn::gelu_derivative(x),
+            Function::Exp => x.clone().map(T::exp),
+            Function::Ln => x.clone().map(T::recip),
+            Function::Square => x.clone().map(|x| x + x),
+        }
+    }
+}
+
+
+/*
+Handle on a node of a tape, it can be copied freely and is only valid for the tape which created it
+ */
+pub struct Variable<'t, T> {
+    tape : &'t Tape<T>,
+    index : usize,
+}
+
+impl<T> Clone for Variable<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Variable<'_, T> {}
+
+
+impl<T> Tape<T> {
+    pub fn new() -> Tape<T> {
+        Tape {
+            nodes : RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize { //number of recorded nodes
+        self.nodes.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /*
+    Record a leaf, the gradients are computed with respect to the leaves
+     */
+    pub fn variable(&self, value : Matrix<T>) -> Variable<'_, T> {
+        self.push(value, Operation::Leaf)
+    }
+
+    fn push(&self, value : Matrix<T>, operation : Operation<T>) -> Variable<'_, T> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node {
+            value,
+            operation,
+        });
+        Variable {
+            tape : self,
+            index : nodes.len() - 1,
+        }
+    }
+}
+
+impl<T> Default for Tape<T> {
+    fn default() -> Self {
+        Tape::new()
+    }
+}
+
+
+//RECORDING THE OPERATIONS
+impl<'t, T> Variable<'t, T> where T : Float + AddAssign {
+    /*
+    Call f with the value of the variable without copying it
+    The tape is borrowed while f runs, so f must not record operations on the same tape
+     */
+    pub fn with_value<R, F>(&self, f : F) -> R where F : FnOnce(&Matrix<T>) -> R {
+        f(&self.tape.nodes.borrow()[self.index].value)
+    }
+
+    pub fn value_cloned(&self) -> Matrix<T> { //a copy, so no borrow of the tape outlives the call and new operations can be recorded
+        self.tape.nodes.borrow()[self.index].value.clone()
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.tape.nodes.borrow()[self.index].value.shape()
+    }
+
+    fn check_tape(&self, rhs : &Variable<'t, T>) {
+        assert!(std::ptr::eq(self.tape, rhs.tape), "Can't combine variables recorded on different tapes");
+    }
+
+    /*
+    Compute the value of the operation from the values of both operands and record it
+     */
+    fn binary<F>(self, rhs : Variable<'t, T>, operation : Operation<T>, f : F) -> Result<Variable<'t, T>, MatrixError> where F : Fn(&Matrix<T>, &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.check_tape(&rhs);
+        let value = {
+            let nodes = self.tape.nodes.borrow();
+            f(&nodes[self.index].value, &nodes[rhs.index].value)?
+        };
+        Ok(self.tape.push(value, operation))
+    }
+
+    fn unary<F>(self, operation : Operation<T>, f : F) -> Variable<'t, T> where F : Fn(&Matrix<T>) -> Matrix<T> {
+        let value = f(&self.tape.nodes.borrow()[self.index].value);
+        self.tape.push(value, operation)
+    }
+
+    pub fn try_mul(self, rhs : Variable<'t, T>) -> Result<Variable<'t, T>, MatrixError> {
+        self.binary(rhs, Operation::Mul(self.index, rhs.index), |a, b| a.try_mul(b))
+    }
+
+    pub fn try_add(self, rhs : Variable<'t, T>) -> Result<Variable<'t, T>, MatrixError> {
+        self.binary(rhs, Operation::Add(self.index, rhs.index), |a, b| a.try_add(b))
+    }
+
+    pub fn try_hadamard(self, rhs : Variable<'t, T>) -> Result<Variable<'t, T>, MatrixError> {
+        self.binary(rhs, Operation::Hadamard(self.index, rhs.index), |a, b| a.try_hadamard(b))
+    }
+
+    pub fn try_add_to_lines(self, line : Variable<'t, T>) -> Result<Variable<'t, T>, MatrixError> {
+        self.binary(line, Operation::AddToLines(self.index, line.index), |a, line| a.clone().try_add_to_lines(line))
+    }
+
+    pub fn add_to_lines(self, line : Variable<'t, T>) -> Variable<'t, T> {
+        self.try_add_to_lines(line).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn sum_line(self) -> Variable<'t, T> { //sum the lines together, the result is a line
+        self.unary(Operation::SumLine(self.index), |a| a.clone().sum_line())
+    }
+
+    pub fn map(self, function : Function<T>) -> Variable<'t, T> {
+        self.unary(Operation::Map(self.index, function), |a| function.apply(a))
+    }
+
+    pub fn t(self) -> Variable<'t, T> {
+        self.unary(Operation::Transpose(self.index), Matrix::t)
+    }
+}
+
+
+//COMPUTING THE GRADIENTS
+/*
+Gradients of a variable with respect to the nodes of its tape
+ */
+pub struct Gradients<'t, T> {
+    tape : &'t Tape<T>,
+    gradients : Vec<Option<Matrix<T>>>,
+}
+
+impl<T> Gradients<'_, T> {
+    /*
+    Gradient with respect to the variable, None if the output doesn't depend on it
+    Panic if the variable was recorded on another tape
+     */
+    pub fn get(&self, variable : Variable<'_, T>) -> Option<&Matrix<T>> {
+        assert!(std::ptr::eq(self.tape, variable.tape), "Can't get the gradient of a variable recorded on another tape");
+        self.gradients.get(variable.index).and_then(Option::as_ref)
+    }
+}
+
+fn accumulate<T>(gradients : &mut [Option<Matrix<T>>], index : usize, gradient : Matrix<T>) where T : Float {
+    gradients[index] = Some(match gradients[index].take() {
+        Some(sum) => sum + gradient,
+        None => gradient,
+    });
+}
+
+impl<'t, T> Variable<'t, T> where T : Float + AddAssign {
+    /*
+    Propagate the gradient from this variable to every node it depends on
+    The output is seen as the sum of its elements, so the seed is a matrix of ones (1 for a 1 x 1 loss)
+     */
+    pub fn backward(&self) -> Gradients<'t, T> {
+        let nodes = self.tape.nodes.borrow();
+        let mut gradients : Vec<Option<Matrix<T>>> = (0..=self.index).map(|_| None).collect();
+        let (nb_lines, nb_columns) = nodes[self.index].value.shape();
+        gradients[self.index] = Some(Matrix::ones(nb_lines, nb_columns));
+        for index in (0..=self.index).rev() {
+            let gradient = match &gradients[index] {
+                Some(gradient) => gradient.clone(),
+                None => continue,
+            };
+            match nodes[index].operation {
+                Operation::Leaf => (),
+                Operation::Mul(a, b) => {
+                    accumulate(&mut gradients, a, &gradient * &nodes[b].value.t());
+                    accumulate(&mut gradients, b, &nodes[a].value.t() * &gradient);
+                }
+                Operation::Add(a, b) => {
+                    accumulate(&mut gradients, a, gradient.clone());
+                    accumulate(&mut gradients, b, gradient);
+                }
+                Operation::Hadamard(a, b) => {
+                    accumulate(&mut gradients, a, gradient.clone() & nodes[b].value.clone());
+                    accumulate(&mut gradients, b, gradient & nodes[a].value.clone());
+                }
+                Operation::AddToLines(a, line) => {
+                    accumulate(&mut gradients, line, gradient.clone().sum_line());
+                    accumulate(&mut gradients, a, gradient);
+                }
+                Operation::SumLine(a) => {
+                    let (nb_lines, nb_columns) = nodes[a].value.shape();
+                    accumulate(&mut gradients, a, Matrix::zeros(nb_lines, nb_columns).add_to_lines(gradient));
+                }
+                Operation::Map(a, function) => {
+                    accumulate(&mut gradients, a, gradient & function.derivative(&nodes[a].value));
+                }
+                Operation::Transpose(a) => {
+                    accumulate(&mut gradients, a, gradient.t());
+                }
+            }
+        }
+        Gradients {
+            tape : self.tape,
+            gradients,
+        }
+    }
+}
+
+
+//OVERLOADING *, + AND & OPERATORS FOR VARIABLES
+impl<'t, T> Mul for Variable<'t, T> where T : Float + AddAssign {
+    type Output = Variable<'t, T>;
+
+    fn mul(self, rhs : Variable<'t, T>) -> Self::Output {
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<'t, T> Add for Variable<'t, T> where T : Float + AddAssign {
+    type Output = Variable<'t, T>;
+
+    fn add(self, rhs : Variable<'t, T>) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<'t, T> BitAnd for Variable<'t, T> where T : Float + AddAssign {
+    type Output = Variable<'t, T>;
+
+    fn bitand(self, rhs : Variable<'t, T>) -> Self::Output {
+        self.try_hadamard(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
@@ -0,0 +1,272 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::{Matrix, MatrixError, MatrixView};
+
+
+/*
+Mini-batches of lines for training loops
+- batches : consecutive blocks of lines, returned as views without copy
+- shuffled_batches : blocks of lines taken in a random order, a new permutation is drawn at each call (one call by epoch)
+    the lines of a batch are not contiguous anymore so they are copied : the iterator returns a new Matrix for each batch,
+    next_batch copies them in a buffer owned by the iterator and lends it, so that only one allocation is done by epoch
+The last batch is smaller when the number of lines is not a multiple of batch_size, call drop_last to skip it
+The paired versions iterate over features and labels together, their lines are taken in the same order
+ */
+impl<T> Matrix<T> {
+    pub fn batches(&self, batch_size : usize) -> Batches<'_, T> {
+        assert!(batch_size > 0, "The size of the batches must be greater than zero");
+        Batches {
+            parent : self,
+            batch_size,
+            front : 0,
+            back : self.nb_lines.div_ceil(batch_size),
+        }
+    }
+
+    pub fn shuffled_batches<R>(&self, batch_size : usize, rng : &mut R) -> ShuffledBatches<'_, T> where R : Rng + ?Sized {
+        let mut permutation = (0..self.nb_lines).collect::<Vec<usize>>();
+        permutation.shuffle(rng);
+        self.batches_in_order(batch_size, permutation)
+    }
+
+    fn batches_in_order(&self, batch_size : usize, permutation : Vec<usize>) -> ShuffledBatches<'_, T> {
+        assert!(batch_size > 0, "The size of the batches must be greater than zero");
+        ShuffledBatches {
+            parent : self,
+            batch_size,
+            permutation,
+            front : 0,
+            back : self.nb_lines.div_ceil(batch_size),
+            buffer : Matrix::new(),
+        }
+    }
+
+    fn check_same_lines<U>(&self, labels : &Matrix<U>) -> Result<(), MatrixError> {
+        if self.nb_lines == labels.nb_lines {
+            Ok(())
+        }
+        else {
+            Err(MatrixError::Pairing(self.shape(), labels.shape()))
+        }
+    }
+
+    pub fn try_paired_batches<'a, U>(&'a self, labels : &'a Matrix<U>, batch_size : usize) -> Result<PairedBatches<'a, T, U>, MatrixError> {
+        self.check_same_lines(labels)?;
+        Ok(PairedBatches {
+            features : self.batches(batch_size),
+            labels : labels.batches(batch_size),
+        })
+    }
+
+    pub fn paired_batches<'a, U>(&'a self, labels : &'a Matrix<U>, batch_size : usize) -> PairedBatches<'a, T, U> {
+        self.try_paired_batches(labels, batch_size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_shuffled_paired_batches<'a, U, R>(&'a self, labels : &'a Matrix<U>, batch_size : usize, rng : &mut R) -> Result<ShuffledPairedBatches<'a, T, U>, MatrixError> where R : Rng + ?Sized {
+        self.check_same_lines(labels)?;
+        let features = self.shuffled_batches(batch_size, rng);
+        let labels = labels.batches_in_order(batch_size, features.permutation.clone());
+        Ok(ShuffledPairedBatches {
+            features,
+            labels,
+        })
+    }
+
+    pub fn shuffled_paired_batches<'a, U, R>(&'a self, labels : &'a Matrix<U>, batch_size : usize, rng : &mut R) -> ShuffledPairedBatches<'a, T, U> where R : Rng + ?Sized {
+        self.try_shuffled_paired_batches(labels, batch_size, rng).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+
+pub struct Batches<'a, T> {
+    parent : &'a Matrix<T>,
+    batch_size : usize,
+    front : usize, //index of the next batch
+    back : usize, //index of the batch following the last one
+}
+impl<T> Batches<'_, T> {
+    pub fn drop_last(mut self) -> Self { //skip the last batch if it is smaller than batch_size
+        self.back = self.back.min(self.parent.nb_lines / self.batch_size);
+        self.front = self.front.min(self.back);
+        self
+    }
+
+    fn lines(&self, k : usize) -> std::ops::Range<usize> {
+        k * self.batch_size..self.parent.nb_lines.min((k + 1) * self.batch_size)
+    }
+}
+impl<'a, T> Iterator for Batches<'a, T> {
+    type Item = MatrixView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.parent.view(self.lines(self.front - 1), ..))
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+impl<T> DoubleEndedIterator for Batches<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.parent.view(self.lines(self.back), ..))
+        }
+        else {
+            None
+        }
+    }
+}
+impl<T> ExactSizeIterator for Batches<'_, T> {}
+
+
+pub struct ShuffledBatches<'a, T> {
+    parent : &'a Matrix<T>,
+    batch_size : usize,
+    permutation : Vec<usize>, //order in which the lines are taken
+    front : usize,
+    back : usize,
+    buffer : Matrix<T>, //reused by next_batch
+}
+impl<T> ShuffledBatches<'_, T> {
+    pub fn drop_last(mut self) -> Self {
+        self.back = self.back.min(self.parent.nb_lines / self.batch_size);
+        self.front = self.front.min(self.back);
+        self
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    fn lines(&self, k : usize) -> std::ops::Range<usize> { //positions in the permutation of the lines of the batch k
+        k * self.batch_size..self.permutation.len().min((k + 1) * self.batch_size)
+    }
+
+    fn batch(&self, k : usize) -> Matrix<T> where T : Copy {
+        self.parent.chose_lines_by_index(self.permutation[self.lines(k)].to_vec())
+    }
+
+    /*
+    Same as next but the lines are copied in the buffer of the iterator, whose allocation is kept from one batch to the next
+     */
+    pub fn next_batch(&mut self) -> Option<&Matrix<T>> where T : Copy {
+        if self.front >= self.back {
+            return None
+        }
+        self.front += 1;
+        let lines = self.lines(self.front - 1);
+        self.buffer.data.clear();
+        for &i in &self.permutation[lines.clone()] {
+            self.buffer.data.extend_from_slice(&self.parent[i]);
+        }
+        self.buffer.nb_lines = lines.len();
+        self.buffer.nb_columns = self.parent.nb_columns;
+        Some(&self.buffer)
+    }
+}
+impl<T> Iterator for ShuffledBatches<'_, T> where T : Copy {
+    type Item = Matrix<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.batch(self.front - 1))
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+impl<T> DoubleEndedIterator for ShuffledBatches<'_, T> where T : Copy {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.batch(self.back))
+        }
+        else {
+            None
+        }
+    }
+}
+impl<T> ExactSizeIterator for ShuffledBatches<'_, T> where T : Copy {}
+
+
+pub struct PairedBatches<'a, T, U> {
+    features : Batches<'a, T>,
+    labels : Batches<'a, U>,
+}
+impl<T, U> PairedBatches<'_, T, U> {
+    pub fn drop_last(self) -> Self {
+        PairedBatches {
+            features : self.features.drop_last(),
+            labels : self.labels.drop_last(),
+        }
+    }
+}
+impl<'a, T, U> Iterator for PairedBatches<'a, T, U> {
+    type Item = (MatrixView<'a, T>, MatrixView<'a, U>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.features.next().zip(self.labels.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.features.size_hint()
+    }
+}
+impl<T, U> DoubleEndedIterator for PairedBatches<'_, T, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.features.next_back().zip(self.labels.next_back())
+    }
+}
+impl<T, U> ExactSizeIterator for PairedBatches<'_, T, U> {}
+
+
+pub struct ShuffledPairedBatches<'a, T, U> {
+    features : ShuffledBatches<'a, T>,
+    labels : ShuffledBatches<'a, U>,
+}
+impl<T, U> ShuffledPairedBatches<'_, T, U> {
+    pub fn drop_last(self) -> Self {
+        ShuffledPairedBatches {
+            features : self.features.drop_last(),
+            labels : self.labels.drop_last(),
+        }
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        self.features.permutation()
+    }
+
+    pub fn next_batch(&mut self) -> Option<(&Matrix<T>, &Matrix<U>)> where T : Copy, U : Copy { //lend the buffers of both iterators
+        self.features.next_batch().zip(self.labels.next_batch())
+    }
+}
+impl<T, U> Iterator for ShuffledPairedBatches<'_, T, U> where T : Copy, U : Copy {
+    type Item = (Matrix<T>, Matrix<U>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.features.next().zip(self.labels.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.features.size_hint()
+    }
+}
+impl<T, U> DoubleEndedIterator for ShuffledPairedBatches<'_, T, U> where T : Copy, U : Copy {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.features.next_back().zip(self.labels.next_back())
+    }
+}
+impl<T, U> ExactSizeIterator for ShuffledPairedBatches<'_, T, U> where T : Copy, U : Copy {}
@@ -0,0 +1,283 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use crate::{FormatError, Matrix};
+
+
+/*
+Compact little-endian checkpoint format, a file contains a named collection of matrices
+- magic : 4 bytes                   b"RMAT"
+- version : u16                     FORMAT_VERSION
+- count : u32                       number of matrices
+- for each matrix :
+    - name length : u32, name : utf-8 bytes     at most MAX_NAME_LENGTH bytes
+    - dtype : u8                    tag of the element type, see BinaryElement
+    - nb_lines : u64, nb_columns : u64
+    - data : nb_lines * nb_columns elements, line by line
+- checksum : u32                    CRC-32 of all the previous bytes
+ */
+const MAGIC : &[u8; 4] = b"RMAT";
+const FORMAT_VERSION : u16 = 1;
+const CHUNK : usize = 1 << 16; //size in bytes of the blocks of data read or written at once
+const MAX_NAME_LENGTH : usize = 1 << 16; //longer names are rejected before allocating them, the length read can't be trusted
+
+
+/*
+Element type which can be stored in the binary format
+ */
+pub trait BinaryElement : Copy {
+    const DTYPE : u8;
+    const SIZE : usize;
+    const NAME : &'static str;
+    fn write_le(&self, bytes : &mut [u8]);
+    fn read_le(bytes : &[u8]) -> Self;
+}
+
+macro_rules! binary_element {
+    ($($t:ty => $dtype:expr),*) => {
+        $(
+            impl BinaryElement for $t {
+                const DTYPE : u8 = $dtype;
+                const SIZE : usize = std::mem::size_of::<$t>();
+                const NAME : &'static str = stringify!($t);
+                fn write_le(&self, bytes : &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+                fn read_le(bytes : &[u8]) -> Self {
+                    let mut array = [0; std::mem::size_of::<$t>()];
+                    array.copy_from_slice(bytes);
+                    <$t>::from_le_bytes(array)
+                }
+            }
+        )*
+    };
+}
+binary_element!(u8 => 1, i8 => 2, u16 => 3, i16 => 4, u32 => 5, i32 => 6, u64 => 7, i64 => 8, f32 => 9, f64 => 10);
+
+impl BinaryElement for bool {
+    const DTYPE : u8 = 11;
+    const SIZE : usize = 1;
+    const NAME : &'static str = "bool";
+    fn write_le(&self, bytes : &mut [u8]) {
+        bytes[0] = *self as u8;
+    }
+    fn read_le(bytes : &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+fn dtype_name(dtype : u8) -> String {
+    match dtype {
+        1 => "u8", 2 => "i8", 3 => "u16", 4 => "i16", 5 => "u32", 6 => "i32",
+        7 => "u64", 8 => "i64", 9 => "f32", 10 => "f64", 11 => "bool",
+        _ => return format!("unknown dtype {}", dtype),
+    }.to_string()
+}
+
+
+//CRC-32 (IEEE)
+const CRC_TABLE : [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            crc = if crc & 1 != 0 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            k += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc_update(crc : u32, bytes : &[u8]) -> u32 {
+    !bytes.iter().fold(!crc, |crc, &byte| CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+struct ChecksumWriter<W> {
+    inner : W,
+    crc : u32,
+}
+impl<W> ChecksumWriter<W> where W : Write {
+    fn put(&mut self, bytes : &[u8]) -> Result<(), FormatError> {
+        self.crc = crc_update(self.crc, bytes);
+        self.inner.write_all(bytes)?;
+        Ok(())
+    }
+}
+
+struct ChecksumReader<R> {
+    inner : R,
+    crc : u32,
+}
+impl<R> ChecksumReader<R> where R : Read {
+    fn take(&mut self, bytes : &mut [u8]) -> Result<(), FormatError> {
+        self.inner.read_exact(bytes)?;
+        self.crc = crc_update(self.crc, bytes);
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8, FormatError> {
+        let mut bytes = [0; 1];
+        self.take(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, FormatError> {
+        let mut bytes = [0; 2];
+        self.take(&mut bytes)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    fn u32(&mut self) -> Result<u32, FormatError> {
+        let mut bytes = [0; 4];
+        self.take(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, FormatError> {
+        let mut bytes = [0; 8];
+        self.take(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+
+//WRITING
+impl<T> Matrix<T> where T : BinaryElement {
+    pub fn write_binary<W>(&self, writer : W) -> Result<(), FormatError> where W : Write {
+        Matrix::write_collection(writer, [("", self)])
+    }
+
+    pub fn save_binary<P>(&self, path : P) -> Result<(), FormatError> where P : AsRef<Path> {
+        self.write_binary(BufWriter::new(File::create(path)?))
+    }
+
+    /*
+    Write a named collection of matrices
+    - matrices : iterator over (name, &Matrix<T>)
+     */
+    pub fn write_collection<'a, W, I, S>(writer : W, matrices : I) -> Result<(), FormatError> where T : 'a, W : Write, I : IntoIterator<Item = (S, &'a Matrix<T>)>, S : AsRef<str> {
+        let matrices : Vec<(S, &Matrix<T>)> = matrices.into_iter().collect();
+        let count = u32::try_from(matrices.len()).map_err(|_| FormatError::InvalidData("Too many matrices in the collection".to_string()))?;
+        let mut writer = ChecksumWriter {
+            inner : writer,
+            crc : 0,
+        };
+        writer.put(MAGIC)?;
+        writer.put(&FORMAT_VERSION.to_le_bytes())?;
+        writer.put(&count.to_le_bytes())?;
+        let mut buffer = vec![0; (CHUNK / T::SIZE).max(1) * T::SIZE];
+        for (name, matrix) in matrices {
+            let name = name.as_ref().as_bytes();
+            if name.len() > MAX_NAME_LENGTH {
+                return Err(FormatError::InvalidData("The name of the matrix is too long".to_string()))
+            }
+            let name_length = name.len() as u32;
+            writer.put(&name_length.to_le_bytes())?;
+            writer.put(name)?;
+            writer.put(&[T::DTYPE])?;
+            writer.put(&(matrix.nb_lines as u64).to_le_bytes())?;
+            writer.put(&(matrix.nb_columns as u64).to_le_bytes())?;
+            for chunk in matrix.data.chunks(buffer.len() / T::SIZE) {
+                let bytes = &mut buffer[..chunk.len() * T::SIZE];
+                for (value, bytes) in chunk.iter().zip(bytes.chunks_mut(T::SIZE)) {
+                    value.write_le(bytes);
+                }
+                writer.put(bytes)?;
+            }
+        }
+        let crc = writer.crc;
+        writer.inner.write_all(&crc.to_le_bytes())?;
+        writer.inner.flush()?;
+        Ok(())
+    }
+
+    pub fn save_collection<'a, P, I, S>(path : P, matrices : I) -> Result<(), FormatError> where T : 'a, P : AsRef<Path>, I : IntoIterator<Item = (S, &'a Matrix<T>)>, S : AsRef<str> {
+        Matrix::write_collection(BufWriter::new(File::create(path)?), matrices)
+    }
+}
+
+
+//READING
+impl<T> Matrix<T> where T : BinaryElement {
+    pub fn read_binary<R>(reader : R) -> Result<Matrix<T>, FormatError> where R : Read {
+        let mut matrices = Matrix::read_collection(reader)?;
+        if matrices.len() != 1 {
+            return Err(FormatError::InvalidData(format!("Expected one matrix, found {}", matrices.len())))
+        }
+        Ok(matrices.remove(0).1)
+    }
+
+    pub fn load_binary<P>(path : P) -> Result<Matrix<T>, FormatError> where P : AsRef<Path> {
+        Matrix::read_binary(BufReader::new(File::open(path)?))
+    }
+
+    /*
+    Read a named collection of matrices, in the order they were written
+     */
+    pub fn read_collection<R>(reader : R) -> Result<Vec<(String, Matrix<T>)>, FormatError> where R : Read {
+        let mut reader = ChecksumReader {
+            inner : reader,
+            crc : 0,
+        };
+        let mut magic = [0; 4];
+        reader.take(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(FormatError::InvalidHeader("This is not a matrix checkpoint".to_string()))
+        }
+        let version = reader.u16()?;
+        if version != FORMAT_VERSION {
+            return Err(FormatError::InvalidHeader(format!("Unsupported version {}", version)))
+        }
+        let count = reader.u32()?;
+        let mut matrices = Vec::new();
+        let mut buffer = vec![0; (CHUNK / T::SIZE).max(1) * T::SIZE];
+        for _ in 0..count {
+            let name_length = reader.u32()? as usize;
+            if name_length > MAX_NAME_LENGTH {
+                return Err(FormatError::InvalidHeader(format!("The length of a name ({} bytes) is greater than the maximum of {} bytes", name_length, MAX_NAME_LENGTH)))
+            }
+            let mut name = vec![0; name_length];
+            reader.take(&mut name)?;
+            let name = String::from_utf8(name).map_err(|_| FormatError::InvalidData("The name of a matrix is not valid utf-8".to_string()))?;
+            let dtype = reader.u8()?;
+            if dtype != T::DTYPE {
+                return Err(FormatError::DtypeMismatch(T::NAME.to_string(), dtype_name(dtype)))
+            }
+            let nb_lines = usize::try_from(reader.u64()?).map_err(|_| FormatError::InvalidData("Too many lines".to_string()))?;
+            let nb_columns = usize::try_from(reader.u64()?).map_err(|_| FormatError::InvalidData("Too many columns".to_string()))?;
+            let size = nb_lines.checked_mul(nb_columns).ok_or_else(|| FormatError::InvalidData("The shape is too large".to_string()))?;
+            let mut data = Vec::new();
+            let mut remaining = size;
+            while remaining > 0 {
+                let nb_elements = remaining.min(buffer.len() / T::SIZE);
+                let bytes = &mut buffer[..nb_elements * T::SIZE];
+                reader.take(bytes)?;
+                data.extend(bytes.chunks(T::SIZE).map(T::read_le));
+                remaining -= nb_elements;
+            }
+            matrices.push((name, Matrix {
+                nb_lines,
+                nb_columns,
+                data,
+            }));
+        }
+        let computed = reader.crc;
+        let mut stored = [0; 4];
+        reader.inner.read_exact(&mut stored)?;
+        let stored = u32::from_le_bytes(stored);
+        if stored != computed {
+            return Err(FormatError::Checksum(stored, computed))
+        }
+        Ok(matrices)
+    }
+
+    pub fn load_collection<P>(path : P) -> Result<Vec<(String, Matrix<T>)>, FormatError> where P : AsRef<Path> {
+        Matrix::read_collection(BufReader::new(File::open(path)?))
+    }
+}
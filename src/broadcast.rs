@@ -0,0 +1,92 @@
+use std::borrow::Borrow;
+use std::ops::{Add, Div, Mul, Sub};
+use crate::{Matrix, MatrixError};
+
+
+/*
+NumPy-style broadcasting between two matrices
+Along each dimension the sizes must be equal or one of them must be 1, the operand of size 1 is then repeated
+So a 1 x n line is applied to every line, a m x 1 column to every column and a 1 x 1 matrix to every element
+The result has the greatest size along each dimension : (m x 1) with (1 x n) gives a m x n matrix
+ */
+fn broadcast_dimension(left : usize, right : usize) -> Option<usize> {
+    if left == right || right == 1 {
+        Some(left)
+    }
+    else if left == 1 {
+        Some(right)
+    }
+    else {
+        None
+    }
+}
+
+impl<T> Matrix<T> {
+    /*
+    Shape of the result of a broadcast operation between self and rhs
+     */
+    pub fn try_broadcast_shape<U>(&self, rhs : &Matrix<U>) -> Result<(usize, usize), MatrixError> {
+        broadcast_dimension(self.nb_lines, rhs.nb_lines)
+            .zip(broadcast_dimension(self.nb_columns, rhs.nb_columns))
+            .ok_or(MatrixError::Broadcasting(self.shape(), rhs.shape()))
+    }
+
+    /*
+    Apply f to the pairs of elements matched by the broadcasting rules
+     */
+    pub fn try_broadcast_with<U, V, F>(&self, rhs : &Matrix<U>, f : F) -> Result<Matrix<V>, MatrixError> where F : Fn(&T, &U) -> V {
+        let (nb_lines, nb_columns) = self.try_broadcast_shape(rhs)?;
+        let mut data = Vec::with_capacity(nb_lines * nb_columns);
+        for i in 0..nb_lines {
+            let left = &self[if self.nb_lines == 1 { 0 } else { i }];
+            let right = &rhs[if rhs.nb_lines == 1 { 0 } else { i }];
+            for j in 0..nb_columns {
+                data.push(f(&left[if self.nb_columns == 1 { 0 } else { j }], &right[if rhs.nb_columns == 1 { 0 } else { j }]));
+            }
+        }
+        Ok(Matrix {
+            nb_lines,
+            nb_columns,
+            data,
+        })
+    }
+
+    pub fn broadcast_with<U, V, F>(&self, rhs : &Matrix<U>, f : F) -> Matrix<V> where F : Fn(&T, &U) -> V {
+        self.try_broadcast_with(rhs, f).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+
+/*
+Named broadcast operations, each with a fallible version
+ */
+macro_rules! broadcast_operation {
+    ($($try_method:ident, $method:ident, $output:ty, [$($bound:tt)*], |$a:ident, $b:ident| $f:expr;)*) => {
+        impl<T> Matrix<T> {
+            $(
+                pub fn $try_method<M>(&self, rhs : M) -> Result<Matrix<$output>, MatrixError> where T : $($bound)*, M : Borrow<Matrix<T>> {
+                    self.try_broadcast_with(rhs.borrow(), |&$a, &$b| $f)
+                }
+
+                pub fn $method<M>(&self, rhs : M) -> Matrix<$output> where T : $($bound)*, M : Borrow<Matrix<T>> {
+                    self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+                }
+            )*
+        }
+    };
+}
+
+broadcast_operation!(
+    try_broadcast_add, broadcast_add, T, [Add<Output = T> + Copy], |a, b| a + b;
+    try_broadcast_sub, broadcast_sub, T, [Sub<Output = T> + Copy], |a, b| a - b;
+    try_broadcast_mul, broadcast_mul, T, [Mul<Output = T> + Copy], |a, b| a * b; //Hadamard product
+    try_broadcast_div, broadcast_div, T, [Div<Output = T> + Copy], |a, b| a / b;
+    try_broadcast_min, broadcast_min, T, [PartialOrd + Copy], |a, b| if b < a { b } else { a };
+    try_broadcast_max, broadcast_max, T, [PartialOrd + Copy], |a, b| if b > a { b } else { a };
+    try_broadcast_eq, broadcast_eq, bool, [PartialEq + Copy], |a, b| a == b;
+    try_broadcast_ne, broadcast_ne, bool, [PartialEq + Copy], |a, b| a != b;
+    try_broadcast_lt, broadcast_lt, bool, [PartialOrd + Copy], |a, b| a < b;
+    try_broadcast_le, broadcast_le, bool, [PartialOrd + Copy], |a, b| a <= b;
+    try_broadcast_gt, broadcast_gt, bool, [PartialOrd + Copy], |a, b| a > b;
+    try_broadcast_ge, broadcast_ge, bool, [PartialOrd + Copy], |a, b| a >= b;
+);
@@ -0,0 +1,334 @@
+use std::borrow::Borrow;
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+use crate::operations::constant;
+
+
+/*
+Cholesky factorization of a symmetric positive definite matrix : A = L * L^T
+Only the lower triangular part of A is read
+ */
+#[derive(Debug, Clone)]
+pub struct Cholesky<T> {
+    l : Matrix<T>,
+}
+
+/*
+LDL^T factorization of a symmetric matrix with Bunch-Kaufman pivoting : P^T * A * P = L * D * L^T
+L is unit lower triangular and D is block diagonal with 1 x 1 and 2 x 2 blocks, so indefinite matrices
+whose leading principal minors are null (like [[0, 1], [1, 0]]) can be factorized
+Only the lower triangular part of A is read
+- ld : Matrix<T>                L in the strictly lower part, the diagonal of D on the diagonal
+- offdiagonal : Vec<T>          offdiagonal[k] is D[k + 1][k], not null only for the first line of a 2 x 2 block
+- permutation : Vec<usize>      line i of P^T * A * P is line permutation[i] of A
+ */
+#[derive(Debug, Clone)]
+pub struct LDLt<T> {
+    ld : Matrix<T>,
+    offdiagonal : Vec<T>,
+    permutation : Vec<usize>,
+}
+
+
+//METHODS TO CREATE THE FACTORIZATIONS
+impl<T> Matrix<T> where T : Float {
+    pub fn cholesky(&self) -> Result<Cholesky<T>, MatrixError> {
+        if self.nb_lines != self.nb_columns {
+            return Err(MatrixError::NotSquare(self.shape()))
+        }
+        let n = self.nb_lines;
+        let mut l = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut diagonal = self[j][j];
+            for k in 0..j {
+                diagonal = diagonal - l[j][k] * l[j][k];
+            }
+            if diagonal <= T::zero() || diagonal.is_nan() {
+                return Err(MatrixError::NotPositiveDefinite)
+            }
+            let diagonal = diagonal.sqrt();
+            l[j][j] = diagonal;
+            for i in j + 1..n {
+                let mut value = self[i][j];
+                for k in 0..j {
+                    value = value - l[i][k] * l[j][k];
+                }
+                l[i][j] = value / diagonal;
+            }
+        }
+        Ok(Cholesky {
+            l,
+        })
+    }
+
+    pub fn ldlt(&self) -> Result<LDLt<T>, MatrixError> {
+        if self.nb_lines != self.nb_columns {
+            return Err(MatrixError::NotSquare(self.shape()))
+        }
+        let n = self.nb_lines;
+        let mut a = Matrix::zeros(n, n); //remaining part of P^T * A * P, kept symmetric
+        for i in 0..n {
+            for j in 0..=i {
+                a[i][j] = self[i][j];
+                a[j][i] = self[i][j];
+            }
+        }
+        let mut ld = Matrix::zeros(n, n);
+        let mut offdiagonal = vec![T::zero(); n.saturating_sub(1)];
+        let mut permutation : Vec<usize> = (0..n).collect();
+        let alpha = (T::one() + constant::<T>(17.).sqrt()) / constant(8.); //bounds the growth of the elements
+        let mut k = 0;
+        while k < n {
+            let absakk = a[k][k].abs();
+            let (imax, colmax) = (k + 1..n).fold((k, T::zero()), |(imax, max), i| if a[i][k].abs() > max { (i, a[i][k].abs()) } else { (imax, max) });
+            if absakk.max(colmax) == T::zero() || absakk.is_nan() {
+                return Err(MatrixError::Singular)
+            }
+            let (pivot, size) = if absakk >= alpha * colmax {
+                (k, 1)
+            }
+            else {
+                let rowmax = (k..n).filter(|&j| j != imax).fold(T::zero(), |max, j| max.max(a[imax][j].abs()));
+                if absakk * rowmax >= alpha * colmax * colmax {
+                    (k, 1)
+                }
+                else if a[imax][imax].abs() >= alpha * rowmax {
+                    (imax, 1)
+                }
+                else {
+                    (imax, 2)
+                }
+            };
+            let swapped = k + size - 1; //line exchanged with the pivot
+            if pivot != swapped {
+                for j in 0..n {
+                    a.data.swap(swapped * n + j, pivot * n + j);
+                }
+                for i in 0..n {
+                    a.data.swap(i * n + swapped, i * n + pivot);
+                }
+                for j in 0..k {
+                    ld.data.swap(swapped * n + j, pivot * n + j);
+                }
+                permutation.swap(swapped, pivot);
+            }
+
+            if size == 1 {
+                let d = a[k][k];
+                ld[k][k] = d;
+                for i in k + 1..n {
+                    ld[i][k] = a[i][k] / d;
+                }
+                for i in k + 1..n {
+                    for j in k + 1..n {
+                        a[i][j] = a[i][j] - ld[i][k] * a[j][k];
+                    }
+                }
+            }
+            else {
+                let (d11, d21, d22) = (a[k][k], a[k + 1][k], a[k + 1][k + 1]);
+                let det = d11 * d22 - d21 * d21;
+                if det == T::zero() || det.is_nan() {
+                    return Err(MatrixError::Singular)
+                }
+                ld[k][k] = d11;
+                ld[k + 1][k + 1] = d22;
+                offdiagonal[k] = d21;
+                for i in k + 2..n { //[l_ik, l_ik+1] = [a_ik, a_ik+1] * D_k^-1
+                    ld[i][k] = (a[i][k] * d22 - a[i][k + 1] * d21) / det;
+                    ld[i][k + 1] = (a[i][k + 1] * d11 - a[i][k] * d21) / det;
+                }
+                for i in k + 2..n {
+                    for j in k + 2..n {
+                        a[i][j] = a[i][j] - ld[i][k] * a[j][k] - ld[i][k + 1] * a[j][k + 1];
+                    }
+                }
+            }
+            k += size;
+        }
+        Ok(LDLt {
+            ld,
+            offdiagonal,
+            permutation,
+        })
+    }
+}
+
+
+//GETTERS
+impl<T> Cholesky<T> where T : Float {
+    pub fn l(&self) -> &Matrix<T> {
+        &self.l
+    }
+
+    pub fn into_l(self) -> Matrix<T> {
+        self.l
+    }
+}
+
+impl<T> LDLt<T> where T : Float {
+    pub fn l(&self) -> Matrix<T> { //return the unit lower triangular factor
+        let n = self.ld.nb_lines;
+        let mut l = Matrix::identity(n);
+        for i in 0..n {
+            l[i][..i].copy_from_slice(&self.ld[i][..i]);
+        }
+        l
+    }
+
+    pub fn d(&self) -> Matrix<T> { //return the block diagonal factor
+        let n = self.ld.nb_lines;
+        let mut d = Matrix::zeros(n, n);
+        for i in 0..n {
+            d[i][i] = self.ld[i][i];
+        }
+        for (k, &value) in self.offdiagonal.iter().enumerate() {
+            d[k + 1][k] = value;
+            d[k][k + 1] = value;
+        }
+        d
+    }
+
+    pub fn p(&self) -> Matrix<T> { //return the permutation matrix
+        let n = self.ld.nb_lines;
+        let mut p = Matrix::zeros(n, n);
+        for (j, &i) in self.permutation.iter().enumerate() {
+            p[i][j] = T::one();
+        }
+        p
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /*
+    Blocks of D as (first index, size)
+     */
+    fn blocks(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let n = self.ld.nb_lines;
+        let mut k = 0;
+        std::iter::from_fn(move || {
+            if k >= n {
+                return None
+            }
+            let size = if k + 1 < n && self.offdiagonal[k] != T::zero() { 2 } else { 1 };
+            k += size;
+            Some((k - size, size))
+        })
+    }
+}
+
+
+//FUNCTIONALITIES
+impl<T> Cholesky<T> where T : Float {
+    /*
+    Solve A * X = B for every column of B
+    - b : Matrix<T>         the right-hand sides, one per column
+     */
+    pub fn solve<M>(&self, b : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
+        let b = b.borrow();
+        let n = self.l.nb_lines;
+        if b.nb_lines != n {
+            return Err(MatrixError::Solving(self.l.shape(), b.shape()))
+        }
+        let mut x = b.clone();
+        for c in 0..x.nb_columns {
+            for i in 0..n { //forward substitution with L
+                let mut value = x[i][c];
+                for k in 0..i {
+                    value = value - self.l[i][k] * x[k][c];
+                }
+                x[i][c] = value / self.l[i][i];
+            }
+            for i in (0..n).rev() { //backward substitution with L^T
+                let mut value = x[i][c];
+                for k in i + 1..n {
+                    value = value - self.l[k][i] * x[k][c];
+                }
+                x[i][c] = value / self.l[i][i];
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Matrix<T> {
+        self.solve(Matrix::identity(self.l.nb_lines)).expect("The identity has the shape of the factorized matrix")
+    }
+
+    pub fn det(&self) -> T {
+        (0..self.l.nb_lines).fold(T::one(), |det, i| det * self.l[i][i] * self.l[i][i])
+    }
+
+    pub fn log_det(&self) -> T { //computed as 2 * sum(ln(l_ii)) to avoid overflows
+        (0..self.l.nb_lines).fold(T::zero(), |log_det, i| log_det + self.l[i][i].ln()) * (T::one() + T::one())
+    }
+}
+
+impl<T> LDLt<T> where T : Float {
+    /*
+    Solve A * X = B for every column of B
+    - b : Matrix<T>         the right-hand sides, one per column
+     */
+    pub fn solve<M>(&self, b : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
+        let b = b.borrow();
+        let n = self.ld.nb_lines;
+        if b.nb_lines != n {
+            return Err(MatrixError::Solving(self.ld.shape(), b.shape()))
+        }
+        let mut x = b.clone();
+        for c in 0..x.nb_columns {
+            let mut y : Vec<T> = self.permutation.iter().map(|&i| b[i][c]).collect(); //P^T * b
+            for i in 0..n { //forward substitution with L
+                for k in 0..i {
+                    y[i] = y[i] - self.ld[i][k] * y[k];
+                }
+            }
+            for (k, size) in self.blocks() { //solving with the blocks of D
+                if size == 1 {
+                    y[k] = y[k] / self.ld[k][k];
+                }
+                else {
+                    let (d11, d21, d22) = (self.ld[k][k], self.offdiagonal[k], self.ld[k + 1][k + 1]);
+                    let det = d11 * d22 - d21 * d21;
+                    let (y1, y2) = (y[k], y[k + 1]);
+                    y[k] = (d22 * y1 - d21 * y2) / det;
+                    y[k + 1] = (d11 * y2 - d21 * y1) / det;
+                }
+            }
+            for i in (0..n).rev() { //backward substitution with L^T
+                for k in i + 1..n {
+                    y[i] = y[i] - self.ld[k][i] * y[k];
+                }
+            }
+            for (i, &line) in self.permutation.iter().enumerate() { //x = P * y
+                x[line][c] = y[i];
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Matrix<T> {
+        self.solve(Matrix::identity(self.ld.nb_lines)).expect("The identity has the shape of the factorized matrix")
+    }
+
+    fn block_det(&self, k : usize, size : usize) -> T {
+        if size == 1 {
+            self.ld[k][k]
+        }
+        else {
+            self.ld[k][k] * self.ld[k + 1][k + 1] - self.offdiagonal[k] * self.offdiagonal[k]
+        }
+    }
+
+    pub fn det(&self) -> T { //the permutation is symmetric so det(A) = det(D)
+        self.blocks().fold(T::one(), |det, (k, size)| det * self.block_det(k, size))
+    }
+
+    pub fn log_det(&self) -> (T, T) { //return the sign of the determinant and the logarithm of its absolute value
+        self.blocks().fold((T::one(), T::zero()), |(sign, log_det), (k, size)| {
+            let det = self.block_det(k, size);
+            (sign * det.signum(), log_det + det.abs().ln())
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+use crate::{FormatError, Matrix};
+
+
+/*
+What to do with an empty field when reading a delimited text file
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingValues<T> {
+    Error, //return FormatError::MissingValue
+    Fill(T), //replace the field by the value
+    NaN, //replace the field by the parsing of "NaN", only works for floating point types
+}
+
+
+/*
+Options used to read and write delimited text files
+- delimiter : character separating the fields
+- header : skip the first line when reading, emit a line of column names when writing
+- column_names : names written in the header, column_0, column_1, ... if empty
+- missing : policy for empty fields when reading
+- precision : number of decimals written, the Display of T is used if None
+Fields may be enclosed in double quotes as in RFC 4180, a quoted field can contain the delimiter and "" stands for a quote,
+but it can't contain a line break : the text is read line by line and an unterminated quote is reported as InvalidData
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions<T> {
+    pub delimiter : char,
+    pub header : bool,
+    pub column_names : Vec<String>,
+    pub missing : MissingValues<T>,
+    pub precision : Option<usize>,
+}
+
+impl<T> Default for CsvOptions<T> {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter : ',',
+            header : false,
+            column_names : Vec::new(),
+            missing : MissingValues::Error,
+            precision : None,
+        }
+    }
+}
+
+/*
+Split a line of text into its fields, the blanks around the fields and the quotes are removed
+- line_number : used in the errors, starts at 1
+ */
+fn split_fields(line : &str, delimiter : char, line_number : usize) -> Result<Vec<Cow<'_, str>>, FormatError> {
+    let is_blank = |c : char| c.is_whitespace() && c != delimiter;
+    let mut fields = Vec::new();
+    let mut rest = line;
+    loop {
+        let field = rest.trim_start_matches(is_blank);
+        let Some(quoted) = field.strip_prefix('"') else {
+            match field.find(delimiter) {
+                Some(end) => {
+                    fields.push(Cow::Borrowed(field[..end].trim_end_matches(is_blank)));
+                    rest = &field[end + delimiter.len_utf8()..];
+                    continue
+                }
+                None => {
+                    fields.push(Cow::Borrowed(field.trim_end_matches(is_blank)));
+                    return Ok(fields)
+                }
+            }
+        };
+
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((k, '"')) if quoted[k + 1..].starts_with('"') => { //escaped quote
+                    value.push('"');
+                    chars.next();
+                }
+                Some((k, '"')) => break k + 1,
+                Some((_, c)) => value.push(c),
+                None => return Err(FormatError::InvalidData(format!("The line {} has an unterminated quoted field, quoted fields can't contain line breaks", line_number))),
+            }
+        };
+        fields.push(Cow::Owned(value));
+        let after = quoted[end..].trim_start_matches(is_blank);
+        if after.is_empty() {
+            return Ok(fields)
+        }
+        rest = after.strip_prefix(delimiter).ok_or_else(|| FormatError::InvalidData(format!("The line {} has characters after a closing quote", line_number)))?;
+    }
+}
+
+fn quote(field : &str, delimiter : char) -> Cow<'_, str> { //quote the field only if it could not be read back otherwise
+    if field.contains([delimiter, '"']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    }
+    else {
+        Cow::Borrowed(field)
+    }
+}
+
+
+//READING
+impl<T> Matrix<T> where T : FromStr + Clone {
+    /*
+    Read a matrix from delimited text, one line of the matrix by line of text, blank lines are ignored
+    The lines and columns reported in the errors start at 1 and count the header
+     */
+    pub fn from_csv<R>(reader : R, options : &CsvOptions<T>) -> Result<Matrix<T>, FormatError> where R : Read {
+        let mut nb_lines = 0;
+        let mut nb_columns = None;
+        let mut data = Vec::new();
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if (i == 0 && options.header) || line.trim().is_empty() {
+                continue
+            }
+            let mut count = 0;
+            for (j, field) in split_fields(&line, options.delimiter, i + 1)?.into_iter().enumerate() {
+                let value = if field.is_empty() {
+                    match &options.missing {
+                        MissingValues::Error => return Err(FormatError::MissingValue(i + 1, j + 1)),
+                        MissingValues::Fill(value) => value.clone(),
+                        MissingValues::NaN => "NaN".parse().map_err(|_| FormatError::Parse(i + 1, j + 1, "NaN".to_string()))?,
+                    }
+                }
+                else {
+                    field.parse().map_err(|_| FormatError::Parse(i + 1, j + 1, field.into_owned()))?
+                };
+                data.push(value);
+                count += 1;
+            }
+            match nb_columns {
+                None => nb_columns = Some(count),
+                Some(expected) if expected != count => return Err(FormatError::InvalidData(format!("The line {} has {} fields instead of {}", i + 1, count, expected))),
+                _ => (),
+            }
+            nb_lines += 1;
+        }
+        Ok(Matrix {
+            nb_lines,
+            nb_columns : nb_columns.unwrap_or(0),
+            data,
+        })
+    }
+}
+
+
+//WRITING
+impl<T> Matrix<T> where T : Display {
+    pub fn to_csv<W>(&self, mut writer : W, options : &CsvOptions<T>) -> Result<(), FormatError> where W : Write {
+        let delimiter = options.delimiter.to_string();
+        if options.header {
+            let names : Vec<String> = if options.column_names.is_empty() {
+                (0..self.nb_columns).map(|j| format!("column_{}", j)).collect()
+            }
+            else if options.column_names.iter().any(|name| name.contains(['\n', '\r'])) {
+                return Err(FormatError::InvalidData("The column names can't contain line breaks".to_string()))
+            }
+            else if options.column_names.len() == self.nb_columns {
+                options.column_names.iter().map(|name| quote(name, options.delimiter).into_owned()).collect()
+            }
+            else {
+                return Err(FormatError::InvalidData(format!("{} column names given for {} columns", options.column_names.len(), self.nb_columns)))
+            };
+            writeln!(writer, "{}", names.join(&delimiter))?;
+        }
+        for i in 0..self.nb_lines {
+            let fields = self[i].iter().map(|value| match options.precision {
+                Some(precision) => format!("{:.*}", precision, value),
+                None => value.to_string(),
+            }).collect::<Vec<String>>();
+            writeln!(writer, "{}", fields.join(&delimiter))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,479 @@
+use std::cmp::Ordering;
+use num_complex::Complex;
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+
+
+/*
+Eigen-decomposition of a symmetric matrix : A = V * diag(eigenvalues) * V^T
+- eigenvalues : Vec<T>          the real eigenvalues sorted by decreasing magnitude
+- eigenvectors : Matrix<T>      the orthonormal eigenvectors in columns, in the order of the eigenvalues
+ */
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen<T> {
+    eigenvalues : Vec<T>,
+    eigenvectors : Matrix<T>,
+}
+
+static MAX_ITERATIONS_PER_EIGENVALUE : usize = 64;
+
+
+//METHODS TO CREATE THE DECOMPOSITIONS
+impl<T> Matrix<T> where T : Float {
+    /*
+    Compute the eigen-decomposition of a symmetric matrix by Householder tridiagonalization followed by the implicit QL algorithm
+    Only the lower triangular part of the matrix is read
+     */
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, MatrixError> {
+        if self.nb_lines != self.nb_columns {
+            return Err(MatrixError::NotSquare(self.shape()))
+        }
+        let n = self.nb_lines;
+        let mut v = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                v[i][j] = self[i][j];
+            }
+        }
+        let mut d = vec![T::zero(); n];
+        let mut e = vec![T::zero(); n];
+        if n > 0 {
+            tridiagonalize(&mut v, &mut d, &mut e);
+            tridiagonal_ql(&mut v, &mut d, &mut e)?;
+        }
+
+        let mut order : Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| d[j].abs().partial_cmp(&d[i].abs()).unwrap_or(Ordering::Equal));
+        Ok(SymmetricEigen {
+            eigenvalues : order.iter().map(|&i| d[i]).collect(),
+            eigenvectors : v.chose_columns_by_index(&order),
+        })
+    }
+
+    /*
+    Compute the eigenvalues of a general real matrix by Hessenberg reduction followed by the shifted QR algorithm
+    The complex eigenvalues come in conjugate pairs, the result is sorted by decreasing magnitude
+     */
+    pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, MatrixError> {
+        if self.nb_lines != self.nb_columns {
+            return Err(MatrixError::NotSquare(self.shape()))
+        }
+        let mut h = self.clone();
+        hessenberg(&mut h);
+        let mut eigenvalues = hessenberg_qr(h)?;
+        eigenvalues.sort_by(|a, b| b.norm().partial_cmp(&a.norm()).unwrap_or(Ordering::Equal));
+        Ok(eigenvalues)
+    }
+}
+
+
+//GETTERS
+impl<T> SymmetricEigen<T> {
+    pub fn eigenvalues(&self) -> &[T] {
+        &self.eigenvalues
+    }
+
+    pub fn eigenvectors(&self) -> &Matrix<T> {
+        &self.eigenvectors
+    }
+}
+
+
+/*
+Householder reduction of the symmetric matrix stored in the lower part of v to a tridiagonal form
+On exit v contains the orthogonal transformation, d the diagonal and e the subdiagonal (e[0] is null)
+ */
+fn tridiagonalize<T>(v : &mut Matrix<T>, d : &mut [T], e : &mut [T]) where T : Float {
+    let n = v.nb_lines;
+    d.copy_from_slice(&v[n - 1]);
+    for i in (1..n).rev() {
+        let scale = d[..i].iter().fold(T::zero(), |sum, x| sum + x.abs());
+        let mut h = T::zero();
+        if scale == T::zero() {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[i - 1][j];
+                v[i][j] = T::zero();
+                v[j][i] = T::zero();
+            }
+        }
+        else {
+            for d_k in d[..i].iter_mut() {
+                *d_k = *d_k / scale;
+                h = h + *d_k * *d_k;
+            }
+            let f = d[i - 1];
+            let mut g = h.sqrt();
+            if f > T::zero() {
+                g = -g;
+            }
+            e[i] = scale * g;
+            h = h - f * g;
+            d[i - 1] = f - g;
+            for e_j in e[..i].iter_mut() {
+                *e_j = T::zero();
+            }
+            for j in 0..i {
+                let f = d[j];
+                v[j][i] = f;
+                let mut g = e[j] + v[j][j] * f;
+                for k in j + 1..i {
+                    g = g + v[k][j] * d[k];
+                    e[k] = e[k] + v[k][j] * f;
+                }
+                e[j] = g;
+            }
+            let mut f = T::zero();
+            for j in 0..i {
+                e[j] = e[j] / h;
+                f = f + e[j] * d[j];
+            }
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] = e[j] - hh * d[j];
+            }
+            for j in 0..i {
+                let (f, g) = (d[j], e[j]);
+                for k in j..i {
+                    v[k][j] = v[k][j] - (f * e[k] + g * d[k]);
+                }
+                d[j] = v[i - 1][j];
+                v[i][j] = T::zero();
+            }
+        }
+        d[i] = h;
+    }
+
+    //accumulation of the transformations
+    for i in 0..n - 1 {
+        v[n - 1][i] = v[i][i];
+        v[i][i] = T::one();
+        let h = d[i + 1];
+        if h != T::zero() {
+            for k in 0..=i {
+                d[k] = v[k][i + 1] / h;
+            }
+            for j in 0..=i {
+                let g = (0..=i).fold(T::zero(), |sum, k| sum + v[k][i + 1] * v[k][j]);
+                for k in 0..=i {
+                    v[k][j] = v[k][j] - g * d[k];
+                }
+            }
+        }
+        for k in 0..=i {
+            v[k][i + 1] = T::zero();
+        }
+    }
+    for j in 0..n {
+        d[j] = v[n - 1][j];
+        v[n - 1][j] = T::zero();
+    }
+    v[n - 1][n - 1] = T::one();
+    e[0] = T::zero();
+}
+
+/*
+Implicit QL algorithm on the tridiagonal matrix (d, e), the rotations are accumulated in v
+On exit d contains the eigenvalues
+ */
+fn tridiagonal_ql<T>(v : &mut Matrix<T>, d : &mut [T], e : &mut [T]) -> Result<(), MatrixError> where T : Float {
+    let n = v.nb_lines;
+    for i in 1..n {
+        e[i - 1] = e[i];
+    }
+    e[n - 1] = T::zero();
+
+    let two = T::one() + T::one();
+    let mut f = T::zero();
+    let mut tst1 = T::zero();
+    for l in 0..n {
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        let mut m = l;
+        while m < n - 1 && e[m].abs() > T::epsilon() * tst1 {
+            m += 1;
+        }
+        if m > l {
+            let mut iterations = 0;
+            loop {
+                iterations += 1;
+                if iterations > MAX_ITERATIONS_PER_EIGENVALUE {
+                    return Err(MatrixError::NoConvergence)
+                }
+                let g = d[l];
+                let mut p = (d[l + 1] - g) / (two * e[l]);
+                let mut r = p.hypot(T::one());
+                if p < T::zero() {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let h = g - d[l];
+                for d_i in d[l + 2..].iter_mut() {
+                    *d_i = *d_i - h;
+                }
+                f = f + h;
+
+                p = d[m];
+                let (mut c, mut c2, mut c3) = (T::one(), T::one(), T::one());
+                let el1 = e[l + 1];
+                let (mut s, mut s2) = (T::zero(), T::zero());
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    let g = c * e[i];
+                    let h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+                    for k in 0..n {
+                        let h = v[k][i + 1];
+                        v[k][i + 1] = s * v[k][i] + c * h;
+                        v[k][i] = c * v[k][i] - s * h;
+                    }
+                }
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+                if e[l].abs() <= T::epsilon() * tst1 {
+                    break
+                }
+            }
+        }
+        d[l] = d[l] + f;
+        e[l] = T::zero();
+    }
+    Ok(())
+}
+
+/*
+Reduction of h to an upper Hessenberg form by orthogonal similarity transformations
+ */
+fn hessenberg<T>(h : &mut Matrix<T>) where T : Float {
+    let n = h.nb_lines;
+    if n < 3 {
+        return
+    }
+    let high = n - 1;
+    let mut ort = vec![T::zero(); n];
+    for m in 1..high {
+        let scale = (m..=high).fold(T::zero(), |sum, i| sum + h[i][m - 1].abs());
+        if scale == T::zero() {
+            continue
+        }
+        let mut norm = T::zero();
+        for i in (m..=high).rev() {
+            ort[i] = h[i][m - 1] / scale;
+            norm = norm + ort[i] * ort[i];
+        }
+        let mut g = norm.sqrt();
+        if ort[m] > T::zero() {
+            g = -g;
+        }
+        norm = norm - ort[m] * g;
+        ort[m] = ort[m] - g;
+        for j in m..n {
+            let f = (m..=high).rev().fold(T::zero(), |sum, i| sum + ort[i] * h[i][j]) / norm;
+            for i in m..=high {
+                h[i][j] = h[i][j] - f * ort[i];
+            }
+        }
+        for i in 0..=high {
+            let f = (m..=high).rev().fold(T::zero(), |sum, j| sum + ort[j] * h[i][j]) / norm;
+            for j in m..=high {
+                h[i][j] = h[i][j] - f * ort[j];
+            }
+        }
+        h[m][m - 1] = scale * g;
+    }
+}
+
+/*
+Shifted QR algorithm (Francis double shift) on the upper Hessenberg matrix h, return its eigenvalues
+ */
+fn hessenberg_qr<T>(mut h : Matrix<T>) -> Result<Vec<Complex<T>>, MatrixError> where T : Float {
+    let nn = h.nb_lines;
+    let mut eigenvalues = Vec::with_capacity(nn);
+    if nn == 0 {
+        return Ok(eigenvalues)
+    }
+    let constant = |value : f64| T::from(value).unwrap_or_else(T::zero);
+    let two = T::one() + T::one();
+    let mut norm = T::zero();
+    for i in 0..nn {
+        for j in i.saturating_sub(1)..nn {
+            norm = norm + h[i][j].abs();
+        }
+    }
+
+    let mut n = nn - 1;
+    let mut exshift = T::zero();
+    let mut iterations = 0;
+    loop {
+        let mut l = n;
+        while l > 0 {
+            let mut s = h[l - 1][l - 1].abs() + h[l][l].abs();
+            if s == T::zero() {
+                s = norm;
+            }
+            if h[l][l - 1].abs() < T::epsilon() * s {
+                break
+            }
+            l -= 1;
+        }
+
+        if l == n { //one root found
+            eigenvalues.push(Complex::new(h[n][n] + exshift, T::zero()));
+            iterations = 0;
+            if n == 0 {
+                break
+            }
+            n -= 1;
+        }
+        else if l == n - 1 { //two roots found
+            let w = h[n][n - 1] * h[n - 1][n];
+            let p = (h[n - 1][n - 1] - h[n][n]) / two;
+            let q = p * p + w;
+            let z = q.abs().sqrt();
+            let x = h[n][n] + exshift;
+            if q >= T::zero() {
+                let z = if p >= T::zero() { p + z } else { p - z };
+                eigenvalues.push(Complex::new(x + z, T::zero()));
+                eigenvalues.push(Complex::new(if z != T::zero() { x - w / z } else { x + z }, T::zero()));
+            }
+            else {
+                eigenvalues.push(Complex::new(x + p, z));
+                eigenvalues.push(Complex::new(x + p, -z));
+            }
+            iterations = 0;
+            if n < 2 {
+                break
+            }
+            n -= 2;
+        }
+        else { //no convergence yet
+            let mut x = h[n][n];
+            let mut y = h[n - 1][n - 1];
+            let mut w = h[n][n - 1] * h[n - 1][n];
+            if iterations == 10 { //exceptional shift
+                exshift = exshift + x;
+                for i in 0..=n {
+                    h[i][i] = h[i][i] - x;
+                }
+                let s = h[n][n - 1].abs() + h[n - 1][n - 2].abs();
+                x = constant(0.75) * s;
+                y = x;
+                w = constant(-0.4375) * s * s;
+            }
+            if iterations == 30 { //MATLAB's exceptional shift
+                let mut s = (y - x) / two;
+                s = s * s + w;
+                if s > T::zero() {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=n {
+                        h[i][i] = h[i][i] - s;
+                    }
+                    exshift = exshift + s;
+                    x = constant(0.964);
+                    y = x;
+                    w = x;
+                }
+            }
+            iterations += 1;
+            if iterations > MAX_ITERATIONS_PER_EIGENVALUE {
+                return Err(MatrixError::NoConvergence)
+            }
+
+            //look for two consecutive small subdiagonal elements
+            let mut m = n - 2;
+            let (mut p, mut q, mut r);
+            loop {
+                let z = h[m][m];
+                r = x - z;
+                let s = y - z;
+                p = (r * s - w) / h[m + 1][m] + h[m][m + 1];
+                q = h[m + 1][m + 1] - z - r - s;
+                r = h[m + 2][m + 1];
+                let s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+                if m == l {
+                    break
+                }
+                if h[m][m - 1].abs() * (q.abs() + r.abs()) < T::epsilon() * (p.abs() * (h[m - 1][m - 1].abs() + z.abs() + h[m + 1][m + 1].abs())) {
+                    break
+                }
+                m -= 1;
+            }
+            for i in m + 2..=n {
+                h[i][i - 2] = T::zero();
+                if i > m + 2 {
+                    h[i][i - 3] = T::zero();
+                }
+            }
+
+            //double QR step on the rows l to n and the columns m to n
+            for k in m..n {
+                let not_last = k != n - 1;
+                if k != m {
+                    p = h[k][k - 1];
+                    q = h[k + 1][k - 1];
+                    r = if not_last { h[k + 2][k - 1] } else { T::zero() };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == T::zero() {
+                        continue
+                    }
+                    p = p / x;
+                    q = q / x;
+                    r = r / x;
+                }
+                let mut s = (p * p + q * q + r * r).sqrt();
+                if p < T::zero() {
+                    s = -s;
+                }
+                if s != T::zero() {
+                    if k != m {
+                        h[k][k - 1] = -s * x;
+                    }
+                    else if l != m {
+                        h[k][k - 1] = -h[k][k - 1];
+                    }
+                    p = p + s;
+                    x = p / s;
+                    y = q / s;
+                    let z = r / s;
+                    q = q / p;
+                    r = r / p;
+                    for j in k..nn {
+                        let mut p = h[k][j] + q * h[k + 1][j];
+                        if not_last {
+                            p = p + r * h[k + 2][j];
+                            h[k + 2][j] = h[k + 2][j] - p * z;
+                        }
+                        h[k][j] = h[k][j] - p * x;
+                        h[k + 1][j] = h[k + 1][j] - p * y;
+                    }
+                    for i in 0..=n.min(k + 3) {
+                        let mut p = x * h[i][k] + y * h[i][k + 1];
+                        if not_last {
+                            p = p + z * h[i][k + 2];
+                            h[i][k + 2] = h[i][k + 2] - p * r;
+                        }
+                        h[i][k] = h[i][k] - p;
+                        h[i][k + 1] = h[i][k + 1] - p * q;
+                    }
+                }
+            }
+        }
+    }
+    Ok(eigenvalues)
+}
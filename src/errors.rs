@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use crate::operations::{ADDING_ERROR, DIVISION_ERROR, HADAMAR_PRODUCT_ERROR, MULTIPLICATION_ERROR, REMAINDER_ERROR, SUBTRACTION_ERROR};
+
+
+/*
+Error returned by the fallible (try_*) methods, each variant carries the shapes (nb_lines, nb_columns) of both operands
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    Adding((usize, usize), (usize, usize)),
+    Subtraction((usize, usize), (usize, usize)),
+    Division((usize, usize), (usize, usize)),
+    Remainder((usize, usize), (usize, usize)),
+    Multiplication((usize, usize), (usize, usize)),
+    Accumulation((usize, usize), (usize, usize)), //the shape of the accumulated matrix and the shape of the product
+    HadamardProduct((usize, usize), (usize, usize)),
+    AddToLines((usize, usize), (usize, usize)),
+    AddToColumns((usize, usize), (usize, usize)),
+    Concatenation((usize, usize), (usize, usize)),
+    BoolSelection((usize, usize), (usize, usize)), //the boolean vector is seen as a column of shape (len, 1)
+    Splitting((usize, usize), usize), //the shape of the matrix and the number of parts asked
+    NotSquare((usize, usize)),
+    Solving((usize, usize), (usize, usize)), //the shape of the system and the shape of the right-hand sides
+    Singular,
+    NotPositiveDefinite,
+    NoConvergence,
+    Conversion((usize, usize), (usize, usize)), //the expected shape and the shape of the converted matrix
+    Pairing((usize, usize), (usize, usize)), //the shapes of the features and of the labels
+    Loss((usize, usize), (usize, usize)), //the shapes of the predictions and of the targets
+    Backward((usize, usize), (usize, usize)), //the shapes of the output of an activation and of the gradient with respect to it
+    EmptyReduction((usize, usize)),
+    Broadcasting((usize, usize), (usize, usize)),
+    Zipping((usize, usize), (usize, usize)),
+    Mask((usize, usize), (usize, usize)), //the shapes of the mask and of the masked matrix
+}
+
+impl MatrixError {
+    fn message(&self) -> &'static str {
+        match self {
+            MatrixError::Adding(..) => ADDING_ERROR,
+            MatrixError::Subtraction(..) => SUBTRACTION_ERROR,
+            MatrixError::Division(..) => DIVISION_ERROR,
+            MatrixError::Remainder(..) => REMAINDER_ERROR,
+            MatrixError::Multiplication(..) => MULTIPLICATION_ERROR,
+            MatrixError::Accumulation(..) => "The matrix to accumulate into must have the shape of the product",
+            MatrixError::HadamardProduct(..) => HADAMAR_PRODUCT_ERROR,
+            MatrixError::AddToLines(_, (nb_lines, _)) if *nb_lines != 1 => "The matrix to add must have exactly one line",
+            MatrixError::AddToLines(..) => "Both matrix must have the same number of columns",
+            MatrixError::AddToColumns(_, (_, nb_columns)) if *nb_columns != 1 => "The matrix to add must have exactly one column",
+            MatrixError::AddToColumns(..) => "Both matrix must have the same number of lines",
+            MatrixError::Concatenation(..) => "Error can't concatenate matrix with different number of columns",
+            MatrixError::BoolSelection(..) => "The length of the provided vector must be the same as the number of line of the matrix",
+            MatrixError::Splitting(_, 0) => "Can't split a matrix in zero parts",
+            MatrixError::Splitting(..) => "You asked to split this matrix in a number of part greater than the number of lines",
+            MatrixError::NotSquare(..) => "The matrix must be square",
+            MatrixError::Solving(..) => "The right-hand sides must have as many lines as the matrix",
+            MatrixError::Singular => "The matrix is singular",
+            MatrixError::NotPositiveDefinite => "The matrix is not positive definite",
+            MatrixError::NoConvergence => "The iterative algorithm did not converge",
+            MatrixError::Conversion(..) => "Can't convert a matrix to a fixed size matrix of a different shape",
+            MatrixError::Pairing(..) => "The features and the labels must have the same number of lines",
+            MatrixError::Loss(..) => "The predictions and the targets must have the same shape",
+            MatrixError::Backward(..) => "The gradient must have the same shape as the output of the activation",
+            MatrixError::EmptyReduction(..) => "Can't select an element along an empty axis",
+            MatrixError::Broadcasting(..) => "Can't broadcast matrices whose sizes differ along a dimension where none of them is 1",
+            MatrixError::Zipping(..) => "Can't zip matrices with different shapes",
+            MatrixError::Mask(..) => "The mask must have the same shape as the matrix",
+        }
+    }
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixError::Splitting(shape, nb_of_parts) => write!(f, "{} (shape : {:?}, parts : {})", self.message(), shape, nb_of_parts),
+            MatrixError::NotSquare(shape) | MatrixError::EmptyReduction(shape) => write!(f, "{} (shape : {:?})", self.message(), shape),
+            MatrixError::Singular | MatrixError::NotPositiveDefinite | MatrixError::NoConvergence => write!(f, "{}", self.message()),
+            MatrixError::Adding(left, right)
+            | MatrixError::Subtraction(left, right)
+            | MatrixError::Division(left, right)
+            | MatrixError::Remainder(left, right)
+            | MatrixError::Multiplication(left, right)
+            | MatrixError::Accumulation(left, right)
+            | MatrixError::HadamardProduct(left, right)
+            | MatrixError::AddToLines(left, right)
+            | MatrixError::AddToColumns(left, right)
+            | MatrixError::Concatenation(left, right)
+            | MatrixError::BoolSelection(left, right)
+            | MatrixError::Solving(left, right)
+            | MatrixError::Conversion(left, right)
+            | MatrixError::Pairing(left, right)
+            | MatrixError::Loss(left, right)
+            | MatrixError::Backward(left, right)
+            | MatrixError::Broadcasting(left, right)
+            | MatrixError::Zipping(left, right)
+            | MatrixError::Mask(left, right) => write!(f, "{} (left : {:?}, right : {:?})", self.message(), left, right),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+
+/*
+Error returned when reading or writing a Matrix from or to a file format
+ */
+#[derive(Debug)]
+pub enum FormatError {
+    Io(io::Error),
+    InvalidHeader(String),
+    DtypeMismatch(String, String), //the expected and the found element types
+    Checksum(u32, u32), //the stored and the computed checksums
+    InvalidData(String),
+    NotTwoDimensional(Vec<usize>), //the shape of the stored array
+    Parse(usize, usize, String), //the line, the column and the field which couldn't be parsed
+    MissingValue(usize, usize), //the line and the column of the empty field
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Io(error) => write!(f, "I/O error : {}", error),
+            FormatError::InvalidHeader(message) => write!(f, "Invalid header : {}", message),
+            FormatError::DtypeMismatch(expected, found) => write!(f, "Can't read elements of type {} as {}", found, expected),
+            FormatError::Checksum(stored, computed) => write!(f, "Corrupted data, the stored checksum {:#010x} doesn't match the computed one {:#010x}", stored, computed),
+            FormatError::InvalidData(message) => write!(f, "Invalid data : {}", message),
+            FormatError::NotTwoDimensional(shape) => write!(f, "Only 2-D arrays can be read as a Matrix, the stored array has the shape {:?}", shape),
+            FormatError::Parse(line, column, field) => write!(f, "Can't parse the field {:?} (line : {}, column : {})", field, line, column),
+            FormatError::MissingValue(line, column) => write!(f, "Missing value (line : {}, column : {})", line, column),
+        }
+    }
+}
+
+impl Error for FormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FormatError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FormatError {
+    fn from(error : io::Error) -> Self {
+        FormatError::Io(error)
+    }
+}
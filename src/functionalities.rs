@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 use rand::distributions::Uniform;
 use rand::{Rng, thread_rng};
-use crate::Matrix;
+use crate::{Matrix, MatrixError};
 
 impl<T> Matrix<T> {
     /*
@@ -24,20 +24,41 @@ impl<T> Matrix<T> {
         }
     }
 
+    /*
+    Create a new Matrix by selecting the columns according to the provided index
+    - chosen_columns : &Vec<usize>          contains the index of the columns to select
+     */
+    pub(crate) fn chose_columns_by_index<V>(&self, chosen_columns : V) -> Matrix<T> where T : Copy, V : Borrow<Vec<usize>> {
+        let chosen_columns = chosen_columns.borrow();
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : chosen_columns.len(),
+            data : {
+                let mut data = Vec::<T>::with_capacity(self.nb_lines * chosen_columns.len());
+                for i in 0..self.nb_lines {
+                    data.extend(chosen_columns.iter().map(|&j| self[i][j]));
+                }
+                data
+            }
+        }
+    }
+
     /*
     Select the provided number of lines using a uniform distribution
      */
     pub fn chose_rnd_lines(&self, nb_of_selected_line : usize) -> Matrix<T> where T : Copy {
         let range = Uniform::new(0, self.nb_lines);
-        self.chose_lines_by_index(&thread_rng().sample_iter(range).take(nb_of_selected_line).collect())
+        self.chose_lines_by_index(thread_rng().sample_iter(range).take(nb_of_selected_line).collect::<Vec<usize>>())
     }
 
     /*
     Select the lines according to the provided boolean vector, if true the line is selected, not otherwise
      */
-    pub fn chose_lines_by_bool<V>(&self, bools : V) -> Matrix<T> where T : Copy, V : Borrow<Vec<bool>> {
+    pub fn try_chose_lines_by_bool<V>(&self, bools : V) -> Result<Matrix<T>, MatrixError> where T : Copy, V : Borrow<Vec<bool>> {
         let bools = bools.borrow();
-        assert_eq!(bools.len(), self.nb_lines, "The length of the provided vector must be the same as the number of line of the matrix");
+        if bools.len() != self.nb_lines {
+            return Err(MatrixError::BoolSelection(self.shape(), (bools.len(), 1)))
+        }
         let mut data = Vec::new();
         let mut nb_lines = 0;
         for i in 0..self.nb_lines {
@@ -46,18 +67,24 @@ impl<T> Matrix<T> {
                 nb_lines +=1;
             }
         }
-        Matrix {
+        Ok(Matrix {
             nb_columns : self.nb_columns,
             nb_lines,
             data,
-        }
+        })
+    }
+
+    pub fn chose_lines_by_bool<V>(&self, bools : V) -> Matrix<T> where T : Copy, V : Borrow<Vec<bool>> {
+        self.try_chose_lines_by_bool(bools).unwrap_or_else(|error| panic!("{}", error))
     }
 
     /*
-    Split the Matrix in the provided number of parts, if the number of parts asked is greater than the number of lines in the Matrix the program panic
+    Split the Matrix in the provided number of parts, the number of parts must be between 1 and the number of lines
      */
-    pub fn split_lines(self, nb_of_parts : usize) -> Vec<Matrix<T>> {
-        assert!(self.nb_lines >= nb_of_parts, "You asked to split this matrix in a number of part greater than the number of lines");
+    pub fn try_split_lines(self, nb_of_parts : usize) -> Result<Vec<Matrix<T>>, MatrixError> {
+        if nb_of_parts == 0 || self.nb_lines < nb_of_parts {
+            return Err(MatrixError::Splitting(self.shape(), nb_of_parts))
+        }
         let main_part = self.nb_lines / nb_of_parts;
         let mut remainder = self.nb_lines - main_part * nb_of_parts;
         let mut sizes = vec![main_part; nb_of_parts];
@@ -70,14 +97,18 @@ impl<T> Matrix<T> {
         let mut res = Vec::with_capacity(nb_of_parts);
         let nb_columns = self.nb_columns;
         let mut matrice_iter = self.into_iter();
-        for i in 0..nb_of_parts {
+        for size in sizes {
             res.push(Matrix {
-                nb_lines: sizes[i],
+                nb_lines: size,
                 nb_columns,
-                data: matrice_iter.by_ref().take(sizes[i] * nb_columns).collect(),
+                data: matrice_iter.by_ref().take(size * nb_columns).collect(),
             })
         }
-        res
+        Ok(res)
+    }
+
+    pub fn split_lines(self, nb_of_parts : usize) -> Vec<Matrix<T>> {
+        self.try_split_lines(nb_of_parts).unwrap_or_else(|error| panic!("{}", error))
     }
 
     /*
@@ -85,18 +116,35 @@ impl<T> Matrix<T> {
     - self : Matrix<T>          The first matrix
     - other : Matrix<T>         The second matrix
      */
-    pub fn concatenate_lines(self, other : Matrix<T>) -> Matrix<T> {
-        assert_eq!(self.nb_columns, other.nb_columns, "Error can't concatenate matrix with different number of columns");
+    pub fn try_concatenate_lines(self, other : Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.nb_columns != other.nb_columns {
+            return Err(MatrixError::Concatenation(self.shape(), other.shape()))
+        }
         let nb_lines = self.nb_lines + other.nb_lines;
         let nb_columns = self.nb_columns;
         let size = nb_lines * self.nb_columns;
         let mut data = Vec::with_capacity(size);
-        data.extend(self.into_iter());
-        data.extend(other.into_iter());
-        Matrix {
+        data.extend(self);
+        data.extend(other);
+        Ok(Matrix {
             nb_lines,
             nb_columns,
             data,
+        })
+    }
+
+    pub fn concatenate_lines(self, other : Matrix<T>) -> Matrix<T> {
+        self.try_concatenate_lines(other).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /*
+    Swap two lines of the Matrix in place
+     */
+    pub fn swap_lines(&mut self, first : usize, second : usize) {
+        if first != second {
+            for j in 0..self.nb_columns {
+                self.data.swap(first * self.nb_columns + j, second * self.nb_columns + j);
+            }
         }
     }
 }
\ No newline at end of file
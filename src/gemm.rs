@@ -0,0 +1,130 @@
+use std::ops::{AddAssign, Mul};
+use num_traits::Zero;
+use crate::{Matrix, MatrixError, MatrixView};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+
+//SIZES OF THE BLOCKS, the packed blocks are allocated once per call on the heap, rayon workers have small stacks
+const MC : usize = 32; //lines of a block of A
+const KC : usize = 64; //columns of a block of A and lines of a panel of B
+const NC : usize = 128; //columns of a panel of B
+const SMALL : usize = 32 * 32 * 32; //under this number of multiplications the blocks are not packed
+#[cfg(feature = "parallel")]
+const PARALLEL : usize = 64 * 64 * 64; //under this number of multiplications the product stays on the current thread
+
+
+/*
+Compute C = alpha * A * B + beta * C
+- a, b : MatrixView<T>          the operands, any Matrix or view (possibly transposed) can be used
+- c : &mut Matrix<T>            must have the shape (a.lines(), b.columns())
+ */
+pub fn gemm<'a, 'b, T, A, B>(alpha : T, a : A, b : B, beta : T, c : &mut Matrix<T>) -> Result<(), MatrixError> where T : Zero + Mul<Output = T> + AddAssign + Copy + 'a + 'b, A : Into<MatrixView<'a, T>>, B : Into<MatrixView<'b, T>> {
+    let (a, b) = (a.into(), b.into());
+    if a.columns() != b.lines() {
+        return Err(MatrixError::Multiplication(a.shape(), b.shape()))
+    }
+    if c.shape() != (a.lines(), b.columns()) {
+        return Err(MatrixError::Accumulation(c.shape(), (a.lines(), b.columns())))
+    }
+    if beta.is_zero() { //C is overwritten, even if it contains NaN
+        c.data.iter_mut().for_each(|value| *value = T::zero());
+    }
+    else {
+        c.data.iter_mut().for_each(|value| *value = *value * beta);
+    }
+    multiply_add(Some(alpha), a, b, &mut c.data);
+    Ok(())
+}
+
+/*
+Compute out = A * B reusing the allocation of out, which is reshaped to (a.lines(), b.columns())
+ */
+pub fn mul_into<'a, 'b, T, A, B>(a : A, b : B, out : &mut Matrix<T>) -> Result<(), MatrixError> where T : Zero + Mul<Output = T> + AddAssign + Copy + 'a + 'b, A : Into<MatrixView<'a, T>>, B : Into<MatrixView<'b, T>> {
+    let (a, b) = (a.into(), b.into());
+    if a.columns() != b.lines() {
+        return Err(MatrixError::Multiplication(a.shape(), b.shape()))
+    }
+    out.nb_lines = a.lines();
+    out.nb_columns = b.columns();
+    out.data.clear();
+    out.data.resize(out.nb_lines * out.nb_columns, T::zero());
+    multiply_add(None, a, b, &mut out.data);
+    Ok(())
+}
+
+/*
+Compute C += alpha * A * B where C is stored line by line, the shapes must already have been checked
+The product is done by blocks : a panel of B and a block of A are packed contiguously so that the inner loop
+walks along the lines of the packed B and of C, which keeps them in cache
+ */
+pub(crate) fn multiply_add<T>(alpha : Option<T>, a : MatrixView<T>, b : MatrixView<T>, c : &mut [T]) where T : Zero + Mul<Output = T> + AddAssign + Copy {
+    let (m, k) = a.shape();
+    let n = b.columns();
+    let scale = |value : T| match alpha {
+        Some(alpha) => alpha * value,
+        None => value,
+    };
+
+    if m * n * k <= SMALL {
+        for i in 0..m {
+            let c_line = &mut c[i * n..(i + 1) * n];
+            for p in 0..k {
+                let a_ip = scale(*a.element(i, p));
+                for (j, c_ij) in c_line.iter_mut().enumerate() {
+                    *c_ij += a_ip * *b.element(p, j);
+                }
+            }
+        }
+        return
+    }
+
+    let mut packed_a = vec![T::zero(); MC.min(m) * KC.min(k)];
+    let mut packed_b = vec![T::zero(); KC.min(k) * NC.min(n)];
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+            for p in 0..kc {
+                for j in 0..nc {
+                    packed_b[p * nc + j] = *b.element(pc + p, jc + j);
+                }
+            }
+            for ic in (0..m).step_by(MC) {
+                let mc = MC.min(m - ic);
+                for i in 0..mc {
+                    for p in 0..kc {
+                        packed_a[i * kc + p] = scale(*a.element(ic + i, pc + p));
+                    }
+                }
+                for i in 0..mc {
+                    let begin = (ic + i) * n + jc;
+                    let c_line = &mut c[begin..begin + nc];
+                    for p in 0..kc {
+                        let a_ip = packed_a[i * kc + p];
+                        for (c_ij, &b_pj) in c_line.iter_mut().zip(&packed_b[p * nc..(p + 1) * nc]) {
+                            *c_ij += a_ip * b_pj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/*
+Same as multiply_add, each rayon thread computes a block of MC lines of C when the product is large enough
+ */
+#[cfg(feature = "parallel")]
+pub(crate) fn par_multiply_add<T>(alpha : Option<T>, a : MatrixView<T>, b : MatrixView<T>, c : &mut [T]) where T : Zero + Mul<Output = T> + AddAssign + Copy + Send + Sync {
+    let (m, k) = a.shape();
+    let n = b.columns();
+    if m * n * k >= PARALLEL && m > MC && n > 0 {
+        c.par_chunks_mut(MC * n).enumerate().for_each(|(block, c_block)| {
+            let begin = block * MC;
+            multiply_add(alpha, a.view(begin..begin + c_block.len() / n, ..), b, c_block);
+        });
+        return
+    }
+    multiply_add(alpha, a, b, c)
+}
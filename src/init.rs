@@ -0,0 +1,95 @@
+use num_traits::Float;
+use rand::Rng;
+use rand::distributions::Uniform;
+use rand::distributions::uniform::SampleUniform;
+use rand_distr::{Normal, StandardNormal, Distribution};
+use crate::Matrix;
+use crate::operations::constant;
+
+
+/*
+Initializers for the weights of a layer computing x * W, with one sample by line of x
+The matrix W has the shape (fan_in, fan_out) : nb_lines inputs and nb_columns outputs
+Every initializer only draws its values from rng, so a seeded rng gives the same matrix each time
+An empty shape gives an empty matrix without drawing anything
+ */
+impl<T> Matrix<T> where T : Float + SampleUniform, StandardNormal : Distribution<T> {
+    /*
+    The fans are not null when the matrix isn't empty, so the limits and the standard deviations are finite and positive
+     */
+    fn scaled_uniform<R>(nb_lines : usize, nb_columns : usize, limit : f64, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        if nb_lines * nb_columns == 0 {
+            return Matrix::zeros(nb_lines, nb_columns)
+        }
+        let limit : T = constant(limit);
+        Matrix::new_rand(nb_lines, nb_columns, rng, Uniform::new_inclusive(-limit, limit))
+    }
+
+    fn scaled_normal<R>(nb_lines : usize, nb_columns : usize, std : f64, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        if nb_lines * nb_columns == 0 {
+            return Matrix::zeros(nb_lines, nb_columns)
+        }
+        let normal = Normal::new(T::zero(), constant(std)).unwrap_or_else(|error| panic!("Invalid standard deviation {} : {}", std, error));
+        Matrix::new_rand(nb_lines, nb_columns, rng, normal)
+    }
+
+    /*
+    Glorot initialization, uniform on [-sqrt(6 / (fan_in + fan_out)), sqrt(6 / (fan_in + fan_out))]
+     */
+    pub fn xavier_uniform<R>(nb_lines : usize, nb_columns : usize, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        Matrix::scaled_uniform(nb_lines, nb_columns, (6. / (nb_lines + nb_columns) as f64).sqrt(), rng)
+    }
+
+    /*
+    Glorot initialization, normal with standard deviation sqrt(2 / (fan_in + fan_out))
+     */
+    pub fn xavier_normal<R>(nb_lines : usize, nb_columns : usize, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        Matrix::scaled_normal(nb_lines, nb_columns, (2. / (nb_lines + nb_columns) as f64).sqrt(), rng)
+    }
+
+    /*
+    He initialization for ReLU layers, uniform on [-sqrt(6 / fan_in), sqrt(6 / fan_in)]
+     */
+    pub fn he_uniform<R>(nb_lines : usize, nb_columns : usize, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        Matrix::scaled_uniform(nb_lines, nb_columns, (6. / nb_lines as f64).sqrt(), rng)
+    }
+
+    /*
+    He initialization for ReLU layers, normal with standard deviation sqrt(2 / fan_in)
+     */
+    pub fn he_normal<R>(nb_lines : usize, nb_columns : usize, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        Matrix::scaled_normal(nb_lines, nb_columns, (2. / nb_lines as f64).sqrt(), rng)
+    }
+
+    /*
+    LeCun initialization for SELU layers, normal with standard deviation sqrt(1 / fan_in)
+     */
+    pub fn lecun_normal<R>(nb_lines : usize, nb_columns : usize, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        Matrix::scaled_normal(nb_lines, nb_columns, (1. / nb_lines as f64).sqrt(), rng)
+    }
+
+    /*
+    Matrix with orthonormal lines or columns (the smallest dimension) multiplied by gain
+    Q is taken from the QR factorization of a gaussian matrix, the columns of Q are multiplied by the signs
+    of the diagonal of R so the result is uniformly distributed among the orthogonal matrices
+     */
+    pub fn orthogonal<R>(nb_lines : usize, nb_columns : usize, gain : T, rng : &mut R) -> Matrix<T> where R : Rng + ?Sized {
+        let (m, n) = (nb_lines.max(nb_columns), nb_lines.min(nb_columns));
+        let gaussian : Matrix<T> = Matrix::new_rand(m, n, rng, StandardNormal);
+        let qr = gaussian.qr();
+        let r = qr.thin_r();
+        let mut q = qr.thin_q();
+        for i in 0..m {
+            for j in 0..n {
+                let sign = if r[j][j] < T::zero() { -T::one() } else { T::one() };
+                q[i][j] = q[i][j] * sign * gain;
+            }
+        }
+        if nb_lines < nb_columns {
+            q.t()
+        }
+        else {
+            q
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Matrix;
+use crate::{Matrix, MatrixView};
 
 
 pub struct MatrixMutRefIterator<'a, T> {
@@ -8,7 +8,7 @@ impl<'a, T> Iterator for MatrixMutRefIterator<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let data = std::mem::replace(&mut self.data, &mut []);
+        let data = std::mem::take(&mut self.data);
         match data.split_first_mut() {
             Some((head, tail)) => {
                 self.data = tail;
@@ -69,4 +69,202 @@ impl<T> IntoIterator for Matrix<T> {
     fn into_iter(self) -> Self::IntoIter {
         self.data.into_iter()
     }
-}
\ No newline at end of file
+}
+
+
+/*
+Iterators over the lines, the columns and the diagonal of a Matrix
+The iterators know their length and can be walked from both ends
+ */
+impl<T> Matrix<T> {
+    pub fn rows(&self) -> Rows<'_, T> { //iterate over the lines as slices
+        Rows {
+            parent : self,
+            front : 0,
+            back : self.nb_lines,
+        }
+    }
+
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            data : &mut self.data,
+            nb_columns : self.nb_columns,
+            remaining : self.nb_lines,
+        }
+    }
+
+    pub fn cols(&self) -> Columns<'_, T> { //iterate over the columns as nb_lines x 1 strided views
+        Columns {
+            parent : self,
+            front : 0,
+            back : self.nb_columns,
+        }
+    }
+
+    pub fn diagonal(&self) -> Diagonal<'_, T> { //iterate over the elements (i, i)
+        Diagonal {
+            parent : self,
+            front : 0,
+            back : self.nb_lines.min(self.nb_columns),
+        }
+    }
+
+    pub fn indexed_iter(&self) -> IndexedIter<'_, T> { //iterate over ((i, j), &element) line by line
+        IndexedIter {
+            parent : self,
+            front : 0,
+            back : self.size(),
+        }
+    }
+
+    pub fn into_rows(self) -> IntoRows<T> { //consume the matrix and return its lines as vectors
+        IntoRows {
+            data : self.data.into_iter(),
+            nb_columns : self.nb_columns,
+            remaining : self.nb_lines,
+        }
+    }
+}
+
+
+/*
+Implement ExactSizeIterator and DoubleEndedIterator for an iterator over the indices front..back of the parent matrix
+ */
+macro_rules! index_iterator {
+    ($name:ident, $item:ty, |$parent:ident, $index:ident| $get:expr) => {
+        impl<'a, T> Iterator for $name<'a, T> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.front < self.back {
+                    let ($parent, $index) = (self.parent, self.front);
+                    self.front += 1;
+                    Some($get)
+                }
+                else {
+                    None
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.back - self.front, Some(self.back - self.front))
+            }
+        }
+        impl<'a, T> DoubleEndedIterator for $name<'a, T> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.front < self.back {
+                    self.back -= 1;
+                    let ($parent, $index) = (self.parent, self.back);
+                    Some($get)
+                }
+                else {
+                    None
+                }
+            }
+        }
+        impl<'a, T> ExactSizeIterator for $name<'a, T> {}
+    };
+}
+
+
+pub struct Rows<'a, T> {
+    parent : &'a Matrix<T>,
+    front : usize,
+    back : usize,
+}
+index_iterator!(Rows, &'a [T], |parent, i| &parent[i]);
+
+
+pub struct Columns<'a, T> {
+    parent : &'a Matrix<T>,
+    front : usize,
+    back : usize,
+}
+index_iterator!(Columns, MatrixView<'a, T>, |parent, j| parent.col(j));
+
+
+pub struct Diagonal<'a, T> {
+    parent : &'a Matrix<T>,
+    front : usize,
+    back : usize,
+}
+index_iterator!(Diagonal, &'a T, |parent, i| &parent[i][i]);
+
+
+pub struct IndexedIter<'a, T> {
+    parent : &'a Matrix<T>,
+    front : usize,
+    back : usize,
+}
+index_iterator!(IndexedIter, ((usize, usize), &'a T), |parent, k| ((k / parent.nb_columns, k % parent.nb_columns), &parent.data[k]));
+
+
+pub struct RowsMut<'a, T> {
+    data : &'a mut [T],
+    nb_columns : usize,
+    remaining : usize,
+}
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        self.remaining -= 1;
+        let (head, tail) = std::mem::take(&mut self.data).split_at_mut(self.nb_columns);
+        self.data = tail;
+        Some(head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<'a, T> DoubleEndedIterator for RowsMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        self.remaining -= 1;
+        let data = std::mem::take(&mut self.data);
+        let (head, tail) = data.split_at_mut(data.len() - self.nb_columns);
+        self.data = head;
+        Some(tail)
+    }
+}
+impl<'a, T> ExactSizeIterator for RowsMut<'a, T> {}
+
+
+pub struct IntoRows<T> {
+    data : std::vec::IntoIter<T>,
+    nb_columns : usize,
+    remaining : usize,
+}
+impl<T> Iterator for IntoRows<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        self.remaining -= 1;
+        Some(self.data.by_ref().take(self.nb_columns).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> DoubleEndedIterator for IntoRows<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        self.remaining -= 1;
+        let mut line = self.data.by_ref().rev().take(self.nb_columns).collect::<Vec<T>>();
+        line.reverse();
+        Some(line)
+    }
+}
+impl<T> ExactSizeIterator for IntoRows<T> {}
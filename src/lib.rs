@@ -7,10 +7,57 @@ use rand::{Rng, distributions::Distribution};
 mod operations;
 mod iterators;
 mod functionalities;
+mod batches;
+mod init;
+mod reductions;
+mod broadcast;
+mod masks;
 mod traitsimpl;
+mod errors;
+mod lu;
+mod qr;
+mod cholesky;
+mod svd;
+mod eigen;
+mod view;
+mod smatrix;
+mod gemm;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod binary;
+mod npy;
+mod csv;
+mod matrix_market;
+pub mod sparse;
+pub mod nn;
+pub mod autograd;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(test)]
 mod test;
 
+pub use errors::{FormatError, MatrixError};
+pub use iterators::{Rows, RowsMut, Columns, Diagonal, IndexedIter, IntoRows};
+pub use reductions::{Axis, Norm};
+pub use batches::{Batches, ShuffledBatches, PairedBatches, ShuffledPairedBatches};
+pub use lu::LU;
+pub use qr::{QR, LeastSquares};
+pub use cholesky::{Cholesky, LDLt};
+pub use svd::{SVD, SVDMode};
+pub use eigen::SymmetricEigen;
+pub use view::{MatrixView, MatrixViewMut, MatrixViewIterator};
+pub use smatrix::{SMatrix, SMatrix2, SMatrix3, SMatrix4};
+pub use gemm::{gemm, mul_into};
+#[cfg(feature = "parallel")]
+pub use parallel::PARALLEL_THRESHOLD;
+pub use binary::BinaryElement;
+pub use npy::NpyElement;
+pub use csv::{CsvOptions, MissingValues};
+pub use matrix_market::{MatrixMarketElement, MatrixMarketFormat};
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "serialization::RawMatrix<T>"))]
 pub struct Matrix<T> {
     nb_lines : usize,
     nb_columns : usize,
@@ -96,6 +143,13 @@ impl<T> Matrix<T> {
     }
 }
 
+//IMPLEMENTATION OF THE DEFAULT TRAIT
+impl<T> Default for Matrix<T> {
+    fn default() -> Self {
+        Matrix::new()
+    }
+}
+
 //IMPLEMENTATION OF THE CLONE TRAIT
 impl<T> Clone for Matrix<T> where T : Clone {
     fn clone(&self) -> Self {
@@ -0,0 +1,173 @@
+use std::borrow::Borrow;
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+
+
+/*
+LU factorization with partial pivoting : P * A = L * U
+- lu : Matrix<T>                L (strictly lower part, unit diagonal implied) and U (upper part) stored together
+- permutation : Vec<usize>      line i of P * A is line permutation[i] of A
+- sign : T                      determinant of P, 1 or -1
+- tolerance : T                 pivots with an absolute value lower or equal to it are considered null
+ */
+#[derive(Debug, Clone)]
+pub struct LU<T> {
+    lu : Matrix<T>,
+    permutation : Vec<usize>,
+    sign : T,
+    tolerance : T,
+}
+
+
+//METHODS TO CREATE THE FACTORIZATION
+impl<T> Matrix<T> where T : Float {
+    pub fn lu(&self) -> Result<LU<T>, MatrixError> { //the default tolerance is epsilon * n * max(|a_ij|)
+        let max = self.data.iter().fold(T::zero(), |max, a| max.max(a.abs()));
+        let n = T::from(self.nb_lines).unwrap_or_else(T::one);
+        self.lu_with_tolerance(T::epsilon() * n * max)
+    }
+
+    pub fn lu_with_tolerance(&self, tolerance : T) -> Result<LU<T>, MatrixError> {
+        if self.nb_lines != self.nb_columns {
+            return Err(MatrixError::NotSquare(self.shape()))
+        }
+        let n = self.nb_lines;
+        let mut lu = self.clone();
+        let mut permutation : Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+        for k in 0..n {
+            let mut pivot = k;
+            for i in k + 1..n {
+                if lu[i][k].abs() > lu[pivot][k].abs() {
+                    pivot = i;
+                }
+            }
+            if pivot != k {
+                lu.swap_lines(pivot, k);
+                permutation.swap(pivot, k);
+                sign = -sign;
+            }
+            if lu[k][k] == T::zero() { //the column is already null under the diagonal
+                continue
+            }
+            for i in k + 1..n {
+                let factor = lu[i][k] / lu[k][k];
+                lu[i][k] = factor;
+                for j in k + 1..n {
+                    let value = lu[k][j];
+                    lu[i][j] = lu[i][j] - factor * value;
+                }
+            }
+        }
+        Ok(LU {
+            lu,
+            permutation,
+            sign,
+            tolerance,
+        })
+    }
+}
+
+
+//GETTERS
+impl<T> LU<T> where T : Float {
+    pub fn l(&self) -> Matrix<T> { //return the unit lower triangular factor
+        let n = self.lu.nb_lines;
+        let mut l = Matrix::identity(n);
+        for i in 0..n {
+            l[i][..i].copy_from_slice(&self.lu[i][..i]);
+        }
+        l
+    }
+
+    pub fn u(&self) -> Matrix<T> { //return the upper triangular factor
+        let n = self.lu.nb_lines;
+        let mut u = Matrix::zeros(n, n);
+        for i in 0..n {
+            u[i][i..].copy_from_slice(&self.lu[i][i..]);
+        }
+        u
+    }
+
+    pub fn p(&self) -> Matrix<T> { //return the permutation matrix
+        let n = self.lu.nb_lines;
+        let mut p = Matrix::zeros(n, n);
+        for (i, &j) in self.permutation.iter().enumerate() {
+            p[i][j] = T::one();
+        }
+        p
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    pub fn tolerance(&self) -> T {
+        self.tolerance
+    }
+}
+
+
+//FUNCTIONALITIES
+impl<T> LU<T> where T : Float {
+    pub fn is_singular(&self) -> bool {
+        (0..self.lu.nb_lines).any(|k| self.lu[k][k].abs() <= self.tolerance)
+    }
+
+    pub fn det(&self) -> T {
+        (0..self.lu.nb_lines).fold(self.sign, |det, k| det * self.lu[k][k])
+    }
+
+    /*
+    Solve A * X = B for every column of B
+    - b : Matrix<T>         the right-hand sides, one per column
+     */
+    pub fn solve<M>(&self, b : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
+        let b = b.borrow();
+        let n = self.lu.nb_lines;
+        if b.nb_lines != n {
+            return Err(MatrixError::Solving(self.lu.shape(), b.shape()))
+        }
+        if self.is_singular() {
+            return Err(MatrixError::Singular)
+        }
+        let mut x = b.chose_lines_by_index(&self.permutation);
+        for c in 0..x.nb_columns {
+            for i in 1..n { //forward substitution with L
+                let mut value = x[i][c];
+                for k in 0..i {
+                    value = value - self.lu[i][k] * x[k][c];
+                }
+                x[i][c] = value;
+            }
+            for i in (0..n).rev() { //backward substitution with U
+                let mut value = x[i][c];
+                for k in i + 1..n {
+                    value = value - self.lu[i][k] * x[k][c];
+                }
+                x[i][c] = value / self.lu[i][i];
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        self.solve(Matrix::identity(self.lu.nb_lines))
+    }
+}
+
+
+//SHORTCUTS ON MATRIX
+impl<T> Matrix<T> where T : Float {
+    pub fn det(&self) -> Result<T, MatrixError> {
+        Ok(self.lu()?.det())
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        self.lu()?.inverse()
+    }
+
+    pub fn solve<M>(&self, b : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
+        self.lu()?.solve(b)
+    }
+}
@@ -0,0 +1,99 @@
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+
+
+/*
+Elementwise masks and the operations using them
+The comparisons are made with a scalar, use the broadcast_* comparisons to compare two matrices
+A mask must have the same shape as the matrix it is applied to
+ */
+
+
+//COMPARISONS
+impl<T> Matrix<T> where T : PartialOrd {
+    pub fn gt(&self, value : T) -> Matrix<bool> {
+        self.map_ref(|x| *x > value)
+    }
+
+    pub fn ge(&self, value : T) -> Matrix<bool> {
+        self.map_ref(|x| *x >= value)
+    }
+
+    pub fn lt(&self, value : T) -> Matrix<bool> {
+        self.map_ref(|x| *x < value)
+    }
+
+    pub fn le(&self, value : T) -> Matrix<bool> {
+        self.map_ref(|x| *x <= value)
+    }
+
+    pub fn eq_elem(&self, value : T) -> Matrix<bool> { //NaN is never equal to anything, use is_nan to find it
+        self.map_ref(|x| *x == value)
+    }
+}
+
+impl<T> Matrix<T> where T : Float {
+    pub fn is_nan(&self) -> Matrix<bool> {
+        self.map_ref(|x| x.is_nan())
+    }
+}
+
+
+//SELECTION AND MASKED ASSIGNMENT
+impl<T> Matrix<T> where T : Clone {
+    /*
+    Take the element of a where the mask is true and the element of b otherwise
+     */
+    pub fn try_select(mask : &Matrix<bool>, a : &Matrix<T>, b : &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        mask.check_same_shape(a, MatrixError::Mask)?;
+        mask.check_same_shape(b, MatrixError::Mask)?;
+        Ok(mask.zip3_with(a, b, |m, x, y| if *m { x.clone() } else { y.clone() }))
+    }
+
+    pub fn select(mask : &Matrix<bool>, a : &Matrix<T>, b : &Matrix<T>) -> Matrix<T> {
+        Matrix::try_select(mask, a, b).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /*
+    Replace the elements where the mask is true by the elements of values at the same position
+     */
+    pub fn try_masked_assign(&mut self, mask : &Matrix<bool>, values : &Matrix<T>) -> Result<(), MatrixError> {
+        mask.check_same_shape(self, MatrixError::Mask)?;
+        mask.check_same_shape(values, MatrixError::Mask)?;
+        for ((x, m), value) in self.data.iter_mut().zip(&mask.data).zip(&values.data) {
+            if *m {
+                *x = value.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn masked_assign(&mut self, mask : &Matrix<bool>, values : &Matrix<T>) {
+        self.try_masked_assign(mask, values).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /*
+    Replace the elements where the mask is true by value
+     */
+    pub fn try_masked_fill(&mut self, mask : &Matrix<bool>, value : T) -> Result<(), MatrixError> {
+        mask.check_same_shape(self, MatrixError::Mask)?;
+        for (x, m) in self.data.iter_mut().zip(&mask.data) {
+            if *m {
+                *x = value.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn masked_fill(&mut self, mask : &Matrix<bool>, value : T) {
+        self.try_masked_fill(mask, value).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+
+//COUNTING
+impl Matrix<bool> {
+    pub fn count_true(&self) -> usize {
+        self.data.iter().filter(|m| **m).count()
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+use num_traits::{One, Zero};
+use crate::{FormatError, Matrix};
+
+
+/*
+Matrix Market exchange format : a banner, comment lines starting with %, a size line then the entries
+- %%MatrixMarket matrix <array | coordinate> <real | integer | pattern> <general | symmetric>
+- array : "nb_lines nb_columns" then one value by line, column by column
+    (only the lower triangle for symmetric matrices)
+- coordinate : "nb_lines nb_columns nb_entries" then "i j value" by line with indices starting at 1
+    (no value for pattern matrices, only the lower triangle for symmetric matrices)
+The duplicate entries of a coordinate file are summed, when reading a dense Matrix as when converting a CooMatrix
+ */
+const BANNER : &str = "%%MatrixMarket";
+const MAX_PREALLOCATION : usize = 1 << 16; //the number of entries of the header can't be trusted, the entries vector grows past it
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixMarketFormat {
+    Array,
+    Coordinate,
+}
+
+
+/*
+Element type which can be read from and written to a Matrix Market file, FIELD is the field written in the banner
+ */
+pub trait MatrixMarketElement : FromStr + Display + Copy + Zero + One + PartialEq {
+    const FIELD : &'static str;
+}
+
+macro_rules! matrix_market_element {
+    ($field:expr => $($t:ty),*) => {
+        $(
+            impl MatrixMarketElement for $t {
+                const FIELD : &'static str = $field;
+            }
+        )*
+    };
+}
+matrix_market_element!("integer" => i8, i16, i32, i64, u8, u16, u32, u64, usize);
+matrix_market_element!("real" => f32, f64);
+
+/*
+Content of a Matrix Market file, the entries of a symmetric matrix are already mirrored
+ */
+pub(crate) struct Entries<T> {
+    pub(crate) nb_lines : usize,
+    pub(crate) nb_columns : usize,
+    pub(crate) triplets : Vec<(usize, usize, T)>, //line, column and value, indices starting at 0
+}
+
+/*
+Iterator over the lines of data with their number, skipping comments and blank lines
+ */
+fn data_lines<R>(lines : R) -> impl Iterator<Item = Result<(usize, String), FormatError>> where R : Iterator<Item = std::io::Result<String>> {
+    lines.enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)).map_err(FormatError::from))
+        .filter(|line| !matches!(line, Ok((_, line)) if line.trim().is_empty() || line.starts_with('%')))
+}
+
+fn parse<V>(field : &str, line : usize, column : usize) -> Result<V, FormatError> where V : FromStr {
+    field.parse().map_err(|_| FormatError::Parse(line, column, field.to_string()))
+}
+
+/*
+Read the entries of an array or coordinate file
+ */
+pub(crate) fn read_entries<T, R>(reader : R) -> Result<Entries<T>, FormatError> where T : MatrixMarketElement, R : Read {
+    let mut lines = BufReader::new(reader).lines();
+    let banner = lines.next().transpose()?.unwrap_or_default().to_lowercase();
+    let banner = banner.split_whitespace().collect::<Vec<&str>>();
+    if banner.len() != 5 || banner[0] != BANNER.to_lowercase() || banner[1] != "matrix" {
+        return Err(FormatError::InvalidHeader(format!("Expected \"{} matrix <format> <field> <symmetry>\"", BANNER)))
+    }
+    let format = match banner[2] {
+        "array" => MatrixMarketFormat::Array,
+        "coordinate" => MatrixMarketFormat::Coordinate,
+        format => return Err(FormatError::InvalidHeader(format!("Unknown format {}", format))),
+    };
+    let pattern = match banner[3] {
+        "pattern" if format == MatrixMarketFormat::Coordinate => true,
+        "integer" => false,
+        "real" if T::FIELD == "real" => false,
+        "real" => return Err(FormatError::DtypeMismatch(T::FIELD.to_string(), "real".to_string())),
+        field => return Err(FormatError::InvalidHeader(format!("Unsupported field {} for the {} format", field, banner[2]))),
+    };
+    let symmetric = match banner[4] {
+        "general" => false,
+        "symmetric" => true,
+        symmetry => return Err(FormatError::InvalidHeader(format!("Unsupported symmetry {}", symmetry))),
+    };
+
+    let mut lines = data_lines(lines);
+    let (number, size) = lines.next().transpose()?.ok_or_else(|| FormatError::InvalidHeader("The size line is missing".to_string()))?;
+    let size = size.split_whitespace().enumerate().map(|(j, field)| parse(field, number, j + 1)).collect::<Result<Vec<usize>, FormatError>>()?;
+    let expected_size = if format == MatrixMarketFormat::Array { 2 } else { 3 };
+    if size.len() != expected_size {
+        return Err(FormatError::InvalidHeader(format!("The size line must contain {} integers", expected_size)))
+    }
+    let (nb_lines, nb_columns) = (size[0], size[1]);
+    if symmetric && nb_lines != nb_columns {
+        return Err(FormatError::InvalidHeader(format!("A symmetric matrix must be square, found the shape {:?}", (nb_lines, nb_columns))))
+    }
+
+    let nb_entries = match format {
+        MatrixMarketFormat::Array if symmetric => nb_lines.checked_add(1).and_then(|n| n.checked_mul(nb_lines)).map(|n| n / 2),
+        MatrixMarketFormat::Array => nb_lines.checked_mul(nb_columns),
+        MatrixMarketFormat::Coordinate => Some(size[2]),
+    }.ok_or_else(|| FormatError::InvalidHeader(format!("The shape {:?} is too large", (nb_lines, nb_columns))))?;
+    let mut triplets = Vec::with_capacity(nb_entries.min(MAX_PREALLOCATION));
+    let mut position = (0, 0); //next position of an array file
+    for k in 0..nb_entries {
+        let (number, line) = lines.next().transpose()?.ok_or_else(|| FormatError::InvalidData(format!("Expected {} entries, found {}", nb_entries, k)))?;
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        let (i, j, value) = match format {
+            MatrixMarketFormat::Array if fields.len() == 1 => {
+                let (i, j) = position;
+                position = if i + 1 < nb_lines { (i + 1, j) } else if symmetric { (j + 1, j + 1) } else { (0, j + 1) };
+                (i, j, parse(fields[0], number, 1)?)
+            }
+            MatrixMarketFormat::Coordinate if fields.len() == if pattern { 2 } else { 3 } => {
+                let i : usize = parse(fields[0], number, 1)?;
+                let j : usize = parse(fields[1], number, 2)?;
+                if i == 0 || j == 0 || i > nb_lines || j > nb_columns {
+                    return Err(FormatError::InvalidData(format!("The entry ({}, {}) at line {} is outside of the matrix", i, j, number)))
+                }
+                let value = if pattern { T::one() } else { parse(fields[2], number, 3)? };
+                (i - 1, j - 1, value)
+            }
+            _ => return Err(FormatError::InvalidData(format!("Unexpected number of fields at line {}", number))),
+        };
+        triplets.push((i, j, value));
+        if symmetric && i != j {
+            triplets.push((j, i, value));
+        }
+    }
+    if let Some((number, _)) = lines.next().transpose()? {
+        return Err(FormatError::InvalidData(format!("Unexpected entry at line {}", number)))
+    }
+    Ok(Entries {
+        nb_lines,
+        nb_columns,
+        triplets,
+    })
+}
+
+/*
+Write the banner and the entries given in the order expected by the format
+For a symmetric file only the entries of the lower triangle must be given
+ */
+pub(crate) fn write_entries<T, W, I>(mut writer : W, format : MatrixMarketFormat, symmetric : bool, shape : (usize, usize), nb_entries : usize, entries : I) -> Result<(), FormatError> where T : MatrixMarketElement, W : Write, I : IntoIterator<Item = (usize, usize, T)> {
+    let symmetry = if symmetric { "symmetric" } else { "general" };
+    match format {
+        MatrixMarketFormat::Array => {
+            writeln!(writer, "{} matrix array {} {}", BANNER, T::FIELD, symmetry)?;
+            writeln!(writer, "{} {}", shape.0, shape.1)?;
+            for (_, _, value) in entries {
+                writeln!(writer, "{}", value)?;
+            }
+        }
+        MatrixMarketFormat::Coordinate => {
+            writeln!(writer, "{} matrix coordinate {} {}", BANNER, T::FIELD, symmetry)?;
+            writeln!(writer, "{} {} {}", shape.0, shape.1, nb_entries)?;
+            for (i, j, value) in entries {
+                writeln!(writer, "{} {} {}", i + 1, j + 1, value)?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+
+impl<T> Matrix<T> where T : MatrixMarketElement {
+    /*
+    Read a dense matrix from an array or a coordinate file, the missing entries of a coordinate file are zeros
+    and its duplicate entries are summed
+     */
+    pub fn from_matrix_market<R>(reader : R) -> Result<Matrix<T>, FormatError> where R : Read {
+        let entries = read_entries(reader)?;
+        if entries.nb_lines.checked_mul(entries.nb_columns).is_none() {
+            return Err(FormatError::InvalidHeader(format!("The shape {:?} is too large for a dense matrix", (entries.nb_lines, entries.nb_columns))))
+        }
+        let mut matrix = Matrix::zeros(entries.nb_lines, entries.nb_columns);
+        for (i, j, value) in entries.triplets {
+            matrix[i][j] = matrix[i][j] + value;
+        }
+        Ok(matrix)
+    }
+
+    /*
+    Write the matrix as a general array, or as a general coordinate file containing the non zero elements
+     */
+    pub fn to_matrix_market<W>(&self, writer : W, format : MatrixMarketFormat) -> Result<(), FormatError> where W : Write {
+        match format {
+            MatrixMarketFormat::Array => {
+                let entries = (0..self.nb_columns).flat_map(|j| (0..self.nb_lines).map(move |i| (i, j, self[i][j])));
+                write_entries(writer, format, false, self.shape(), self.size(), entries)
+            }
+            MatrixMarketFormat::Coordinate => {
+                let entries = (0..self.nb_lines)
+                    .flat_map(|i| (0..self.nb_columns).map(move |j| (i, j, self[i][j])))
+                    .filter(|(_, _, value)| !value.is_zero())
+                    .collect::<Vec<(usize, usize, T)>>();
+                write_entries(writer, format, false, self.shape(), entries.len(), entries)
+            }
+        }
+    }
+
+    /*
+    Write a symmetric matrix as a symmetric file containing only its lower triangle
+    FormatError::InvalidData is returned if the matrix is not symmetric
+     */
+    pub fn to_matrix_market_symmetric<W>(&self, writer : W, format : MatrixMarketFormat) -> Result<(), FormatError> where W : Write {
+        let n = self.nb_lines;
+        if self.nb_columns != n || (0..n).any(|i| (0..i).any(|j| self[i][j] != self[j][i])) {
+            return Err(FormatError::InvalidData("Only a symmetric matrix can be written as a symmetric file".to_string()))
+        }
+        let lower = (0..n).flat_map(|j| (j..n).map(move |i| (i, j, self[i][j])));
+        match format {
+            MatrixMarketFormat::Array => write_entries(writer, format, true, self.shape(), n * (n + 1) / 2, lower),
+            MatrixMarketFormat::Coordinate => {
+                let entries = lower.filter(|(_, _, value)| !value.is_zero()).collect::<Vec<(usize, usize, T)>>();
+                write_entries(writer, format, true, self.shape(), entries.len(), entries)
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+use crate::operations::constant;
+
+
+/*
+Activation and loss functions for neural networks
+- the activations are applied elementwise except softmax and log_softmax which are applied line by line (one sample by line)
+- the derivatives of the elementwise activations are taken at the input of the activation
+- softmax_backward and log_softmax_backward take the output of the activation and the gradient with respect to it,
+    and return the gradient with respect to the input
+- the losses are averaged and return the scalar loss and its gradient with respect to the predictions
+ */
+
+
+fn apply<T, F>(x : &Matrix<T>, f : F) -> Matrix<T> where T : Float, F : Fn(T) -> T {
+    Matrix {
+        nb_lines : x.nb_lines,
+        nb_columns : x.nb_columns,
+        data : x.data.iter().map(|&x| f(x)).collect(),
+    }
+}
+
+
+
+//ACTIVATIONS
+fn sigmoid_scalar<T>(x : T) -> T where T : Float {
+    if x >= T::zero() { //avoid the overflow of exp(-x) for large negative x
+        T::one() / (T::one() + (-x).exp())
+    }
+    else {
+        let e = x.exp();
+        e / (T::one() + e)
+    }
+}
+
+pub fn sigmoid<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    apply(x, sigmoid_scalar)
+}
+
+pub fn sigmoid_derivative<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    apply(x, |x| {
+        let s = sigmoid_scalar(x);
+        s * (T::one() - s)
+    })
+}
+
+pub fn tanh<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    apply(x, T::tanh)
+}
+
+pub fn tanh_derivative<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    apply(x, |x| T::one() - x.tanh().powi(2))
+}
+
+pub fn relu<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    apply(x, |x| x.max(T::zero()))
+}
+
+pub fn relu_derivative<T>(x : &Matrix<T>) -> Matrix<T> where T : Float { //0 is used as derivative at 0
+    apply(x, |x| if x > T::zero() { T::one() } else { T::zero() })
+}
+
+pub fn leaky_relu<T>(x : &Matrix<T>, alpha : T) -> Matrix<T> where T : Float {
+    apply(x, move |x| if x > T::zero() { x } else { alpha * x })
+}
+
+pub fn leaky_relu_derivative<T>(x : &Matrix<T>, alpha : T) -> Matrix<T> where T : Float {
+    apply(x, move |x| if x > T::zero() { T::one() } else { alpha })
+}
+
+/*
+GELU with the tanh approximation 0.5 * x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3)))
+ */
+const GELU_COEFFICIENT : f64 = 0.044715;
+
+pub fn gelu<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    let c : T = constant(std::f64::consts::FRAC_2_PI.sqrt());
+    let a : T = constant(GELU_COEFFICIENT);
+    let half : T = constant(0.5);
+    apply(x, move |x| half * x * (T::one() + (c * (x + a * x.powi(3))).tanh()))
+}
+
+pub fn gelu_derivative<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    let c : T = constant(std::f64::consts::FRAC_2_PI.sqrt());
+    let a : T = constant(GELU_COEFFICIENT);
+    let half : T = constant(0.5);
+    let three : T = constant(3.);
+    apply(x, move |x| {
+        let th = (c * (x + a * x.powi(3))).tanh();
+        half * (T::one() + th) + half * x * (T::one() - th * th) * c * (T::one() + three * a * x * x)
+    })
+}
+
+/*
+The maximum of each line is subtracted before taking the exponentials so they can't overflow
+ */
+pub fn softmax<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    let mut res = x.clone();
+    for line in res.rows_mut() {
+        let max = line.iter().fold(T::neg_infinity(), |max, &x| max.max(x));
+        let mut sum = T::zero();
+        for x in line.iter_mut() {
+            *x = (*x - max).exp();
+            sum = sum + *x;
+        }
+        for x in line.iter_mut() {
+            *x = *x / sum;
+        }
+    }
+    res
+}
+
+pub fn log_softmax<T>(x : &Matrix<T>) -> Matrix<T> where T : Float {
+    let mut res = x.clone();
+    for line in res.rows_mut() {
+        let max = line.iter().fold(T::neg_infinity(), |max, &x| max.max(x));
+        let log_sum = line.iter().fold(T::zero(), |sum, &x| sum + (x - max).exp()).ln() + max;
+        for x in line.iter_mut() {
+            *x = *x - log_sum;
+        }
+    }
+    res
+}
+
+/*
+Gradient with respect to the input of softmax : s * (g - sum(g * s)) on each line
+- output : the result of softmax
+- gradient : the gradient with respect to the output
+ */
+pub fn try_softmax_backward<T>(output : &Matrix<T>, gradient : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Float {
+    output.check_same_shape(gradient, MatrixError::Backward)?;
+    let mut res = gradient.clone();
+    for (i, line) in res.rows_mut().enumerate() {
+        let dot = line.iter().zip(&output[i]).fold(T::zero(), |sum, (&g, &s)| sum + g * s);
+        for (g, &s) in line.iter_mut().zip(&output[i]) {
+            *g = s * (*g - dot);
+        }
+    }
+    Ok(res)
+}
+
+pub fn softmax_backward<T>(output : &Matrix<T>, gradient : &Matrix<T>) -> Matrix<T> where T : Float {
+    try_softmax_backward(output, gradient).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/*
+Gradient with respect to the input of log_softmax : g - exp(output) * sum(g) on each line
+ */
+pub fn try_log_softmax_backward<T>(output : &Matrix<T>, gradient : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Float {
+    output.check_same_shape(gradient, MatrixError::Backward)?;
+    let mut res = gradient.clone();
+    for (i, line) in res.rows_mut().enumerate() {
+        let sum = line.iter().fold(T::zero(), |sum, &g| sum + g);
+        for (g, &o) in line.iter_mut().zip(&output[i]) {
+            *g = *g - o.exp() * sum;
+        }
+    }
+    Ok(res)
+}
+
+pub fn log_softmax_backward<T>(output : &Matrix<T>, gradient : &Matrix<T>) -> Matrix<T> where T : Float {
+    try_log_softmax_backward(output, gradient).unwrap_or_else(|error| panic!("{}", error))
+}
+
+
+
+//LOSSES
+/*
+Mean squared error over all the elements : mean((predictions - targets)^2)
+ */
+pub fn try_mse<T>(predictions : &Matrix<T>, targets : &Matrix<T>) -> Result<(T, Matrix<T>), MatrixError> where T : Float {
+    predictions.check_same_shape(targets, MatrixError::Loss)?;
+    let n : T = constant(predictions.size() as f64);
+    let two : T = constant(2.);
+    let loss = predictions.data.iter().zip(&targets.data).fold(T::zero(), |sum, (&p, &t)| sum + (p - t).powi(2)) / n;
+    let gradient = Matrix {
+        nb_lines : predictions.nb_lines,
+        nb_columns : predictions.nb_columns,
+        data : predictions.data.iter().zip(&targets.data).map(|(&p, &t)| two * (p - t) / n).collect(),
+    };
+    Ok((loss, gradient))
+}
+
+pub fn mse<T>(predictions : &Matrix<T>, targets : &Matrix<T>) -> (T, Matrix<T>) where T : Float {
+    try_mse(predictions, targets).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/*
+Cross-entropy between the softmax of the logits and the target distributions, averaged over the lines
+- logits : unnormalized scores, one sample by line
+- targets : probabilities summing to one on each line (one-hot encoded classes for example)
+The gradient with respect to the logits is (softmax(logits) - targets) / nb_lines
+ */
+pub fn try_cross_entropy<T>(logits : &Matrix<T>, targets : &Matrix<T>) -> Result<(T, Matrix<T>), MatrixError> where T : Float {
+    logits.check_same_shape(targets, MatrixError::Loss)?;
+    let n : T = constant(logits.nb_lines as f64);
+    let log_probabilities = log_softmax(logits);
+    let loss = -log_probabilities.data.iter().zip(&targets.data).fold(T::zero(), |sum, (&l, &t)| if t == T::zero() { sum } else { sum + t * l }) / n;
+    let gradient = Matrix {
+        nb_lines : logits.nb_lines,
+        nb_columns : logits.nb_columns,
+        data : log_probabilities.data.iter().zip(&targets.data).map(|(&l, &t)| (l.exp() - t) / n).collect(),
+    };
+    Ok((loss, gradient))
+}
+
+pub fn cross_entropy<T>(logits : &Matrix<T>, targets : &Matrix<T>) -> (T, Matrix<T>) where T : Float {
+    try_cross_entropy(logits, targets).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/*
+Binary cross-entropy averaged over all the elements : -mean(t * ln(p) + (1 - t) * ln(1 - p))
+- predictions : probabilities, the output of sigmoid for example, clamped to [eps, 1 - eps] to keep the logarithms finite
+- targets : values in [0, 1]
+ */
+pub fn try_binary_cross_entropy<T>(predictions : &Matrix<T>, targets : &Matrix<T>) -> Result<(T, Matrix<T>), MatrixError> where T : Float {
+    predictions.check_same_shape(targets, MatrixError::Loss)?;
+    let n : T = constant(predictions.size() as f64);
+    let eps = constant::<T>(1e-7).max(T::epsilon());
+    let clamp = move |p : T| p.max(eps).min(T::one() - eps);
+    let loss = -predictions.data.iter().zip(&targets.data).fold(T::zero(), |sum, (&p, &t)| {
+        let p = clamp(p);
+        sum + t * p.ln() + (T::one() - t) * (T::one() - p).ln()
+    }) / n;
+    let gradient = Matrix {
+        nb_lines : predictions.nb_lines,
+        nb_columns : predictions.nb_columns,
+        data : predictions.data.iter().zip(&targets.data).map(|(&p, &t)| {
+            let p = clamp(p);
+            (p - t) / (p * (T::one() - p)) / n
+        }).collect(),
+    };
+    Ok((loss, gradient))
+}
+
+pub fn binary_cross_entropy<T>(predictions : &Matrix<T>, targets : &Matrix<T>) -> (T, Matrix<T>) where T : Float {
+    try_binary_cross_entropy(predictions, targets).unwrap_or_else(|error| panic!("{}", error))
+}
@@ -0,0 +1,248 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+#[cfg(feature = "npz")]
+use std::io::Seek;
+use std::path::Path;
+#[cfg(feature = "npz")]
+use zip::result::ZipError;
+#[cfg(feature = "npz")]
+use zip::write::FileOptions;
+#[cfg(feature = "npz")]
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use crate::{BinaryElement, FormatError, Matrix};
+
+
+/*
+NumPy .npy format : magic string, version, little-endian header length, python dict literal header, raw data
+The header looks like {'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }
+ */
+const MAGIC : &[u8; 6] = b"\x93NUMPY";
+const ALIGNMENT : usize = 64;
+const MAX_HEADER_LENGTH : usize = 10000; //same limit as NumPy, the length is read from the file so it can't be trusted
+
+
+/*
+Element type which can be stored in a .npy file, DESCR is the little-endian NumPy type string
+ */
+pub trait NpyElement : BinaryElement {
+    const DESCR : &'static str;
+}
+impl NpyElement for f32 { const DESCR : &'static str = "<f4"; }
+impl NpyElement for f64 { const DESCR : &'static str = "<f8"; }
+impl NpyElement for i32 { const DESCR : &'static str = "<i4"; }
+impl NpyElement for i64 { const DESCR : &'static str = "<i8"; }
+impl NpyElement for u8 { const DESCR : &'static str = "|u1"; }
+impl NpyElement for bool { const DESCR : &'static str = "|b1"; }
+
+struct Header {
+    descr : String,
+    fortran_order : bool,
+    shape : Vec<usize>,
+}
+
+/*
+Return the text following the key in the python dict literal
+ */
+fn value_of<'a>(header : &'a str, key : &str) -> Result<&'a str, FormatError> {
+    ["'", "\""].iter()
+        .find_map(|quote| header.find(&format!("{}{}{}", quote, key, quote)).map(|index| index + key.len() + 2))
+        .and_then(|index| header[index..].trim_start().strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or_else(|| FormatError::InvalidHeader(format!("The key '{}' is missing", key)))
+}
+
+fn parse_header(header : &str) -> Result<Header, FormatError> {
+    let descr = value_of(header, "descr")?;
+    let quote = descr.chars().next().filter(|c| *c == '\'' || *c == '"').ok_or_else(|| FormatError::InvalidHeader("The descr is not a string".to_string()))?;
+    let descr = descr[1..].split(quote).next().unwrap_or_default().to_string();
+
+    let fortran_order = value_of(header, "fortran_order")?;
+    let fortran_order = if fortran_order.starts_with("True") {
+        true
+    }
+    else if fortran_order.starts_with("False") {
+        false
+    }
+    else {
+        return Err(FormatError::InvalidHeader("fortran_order must be True or False".to_string()))
+    };
+
+    let shape = value_of(header, "shape")?;
+    let shape = shape.strip_prefix('(')
+        .and_then(|shape| shape.split(')').next())
+        .ok_or_else(|| FormatError::InvalidHeader("The shape is not a tuple".to_string()))?;
+    let shape = shape.split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse().map_err(|_| FormatError::InvalidHeader(format!("Invalid dimension {}", dimension))))
+        .collect::<Result<Vec<usize>, FormatError>>()?;
+    Ok(Header {
+        descr,
+        fortran_order,
+        shape,
+    })
+}
+
+
+//READING
+impl<T> Matrix<T> where T : NpyElement {
+    pub fn read_npy<P>(path : P) -> Result<Matrix<T>, FormatError> where P : AsRef<Path> {
+        Matrix::read_npy_from(BufReader::new(File::open(path)?))
+    }
+
+    /*
+    Read a 2-D array stored in C or Fortran order, big-endian data is converted
+     */
+    pub fn read_npy_from<R>(mut reader : R) -> Result<Matrix<T>, FormatError> where R : Read {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic[..6] != MAGIC {
+            return Err(FormatError::InvalidHeader("This is not a .npy file".to_string()))
+        }
+        let header_length = match magic[6] {
+            1 => {
+                let mut length = [0; 2];
+                reader.read_exact(&mut length)?;
+                u16::from_le_bytes(length) as usize
+            }
+            2 | 3 => {
+                let mut length = [0; 4];
+                reader.read_exact(&mut length)?;
+                u32::from_le_bytes(length) as usize
+            }
+            version => return Err(FormatError::InvalidHeader(format!("Unsupported version {}.{}", version, magic[7]))),
+        };
+        if header_length > MAX_HEADER_LENGTH {
+            return Err(FormatError::InvalidHeader(format!("The header length {} is larger than {}", header_length, MAX_HEADER_LENGTH)))
+        }
+        let mut header = vec![0; header_length];
+        reader.read_exact(&mut header)?;
+        let header = parse_header(&String::from_utf8_lossy(&header))?;
+
+        if header.shape.len() != 2 {
+            return Err(FormatError::NotTwoDimensional(header.shape))
+        }
+        let (byte_order, kind) = header.descr.split_at(header.descr.len().min(1));
+        let (_, expected_kind) = T::DESCR.split_at(1);
+        if kind != expected_kind || !matches!(byte_order, "<" | ">" | "|" | "=") {
+            return Err(FormatError::DtypeMismatch(T::DESCR.to_string(), header.descr))
+        }
+
+        let (nb_lines, nb_columns) = (header.shape[0], header.shape[1]);
+        let size = nb_lines.checked_mul(nb_columns).ok_or_else(|| FormatError::InvalidData("The shape is too large".to_string()))?;
+        let mut bytes = vec![0; T::SIZE];
+        let mut data = Vec::new();
+        for _ in 0..size {
+            reader.read_exact(&mut bytes)?;
+            if byte_order == ">" {
+                bytes.reverse();
+            }
+            data.push(T::read_le(&bytes));
+        }
+        let matrix = Matrix {
+            nb_lines,
+            nb_columns,
+            data,
+        };
+        if header.fortran_order { //the data was stored column by column
+            let transposed = Matrix {
+                nb_lines : nb_columns,
+                nb_columns : nb_lines,
+                data : matrix.data,
+            };
+            Ok(transposed.t())
+        }
+        else {
+            Ok(matrix)
+        }
+    }
+}
+
+
+//WRITING
+impl<T> Matrix<T> where T : NpyElement {
+    pub fn write_npy<P>(&self, path : P) -> Result<(), FormatError> where P : AsRef<Path> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /*
+    Write the matrix as a 2-D little-endian array in C order
+     */
+    pub fn write_npy_to<W>(&self, mut writer : W) -> Result<(), FormatError> where W : Write {
+        let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}", T::DESCR, self.nb_lines, self.nb_columns);
+        let version : u8 = if header.len() + 11 <= u16::MAX as usize { 1 } else { 2 };
+        let prefix = if version == 1 { 10 } else { 12 };
+        let padding = (ALIGNMENT - (prefix + header.len() + 1) % ALIGNMENT) % ALIGNMENT;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[version, 0])?;
+        if version == 1 {
+            writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        }
+        else {
+            writer.write_all(&(header.len() as u32).to_le_bytes())?;
+        }
+        writer.write_all(header.as_bytes())?;
+        let mut bytes = vec![0; T::SIZE];
+        for value in &self.data {
+            value.write_le(&mut bytes);
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+}
+
+
+//.NPZ ARCHIVES, available with the npz feature
+#[cfg(feature = "npz")]
+impl<T> Matrix<T> where T : NpyElement {
+    /*
+    Read every array of a .npz archive, the names don't contain the .npy extension
+     */
+    pub fn read_npz<P>(path : P) -> Result<Vec<(String, Matrix<T>)>, FormatError> where P : AsRef<Path> {
+        Matrix::read_npz_from(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read_npz_from<R>(reader : R) -> Result<Vec<(String, Matrix<T>)>, FormatError> where R : Read + Seek {
+        let mut archive = ZipArchive::new(reader).map_err(zip_error)?;
+        let mut matrices = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let file = archive.by_index(i).map_err(zip_error)?;
+            let name = file.name().strip_suffix(".npy").unwrap_or(file.name()).to_string();
+            matrices.push((name, Matrix::read_npy_from(BufReader::new(file))?));
+        }
+        Ok(matrices)
+    }
+
+    /*
+    Write a .npz archive containing one array per matrix, compressed with deflate if compressed is true
+     */
+    pub fn write_npz<'a, P, I, S>(path : P, matrices : I, compressed : bool) -> Result<(), FormatError> where T : 'a, P : AsRef<Path>, I : IntoIterator<Item = (S, &'a Matrix<T>)>, S : AsRef<str> {
+        Matrix::write_npz_to(File::create(path)?, matrices, compressed)
+    }
+
+    pub fn write_npz_to<'a, W, I, S>(writer : W, matrices : I, compressed : bool) -> Result<(), FormatError> where T : 'a, W : Write + Seek, I : IntoIterator<Item = (S, &'a Matrix<T>)>, S : AsRef<str> {
+        let mut archive = ZipWriter::new(writer);
+        let method = if compressed { CompressionMethod::Deflated } else { CompressionMethod::Stored };
+        let options = FileOptions::default().compression_method(method).large_file(true);
+        for (name, matrix) in matrices {
+            archive.start_file(format!("{}.npy", name.as_ref()), options).map_err(zip_error)?;
+            matrix.write_npy_to(&mut archive)?;
+        }
+        archive.finish().map_err(zip_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "npz")]
+fn zip_error(error : ZipError) -> FormatError {
+    match error {
+        ZipError::Io(error) => FormatError::Io(error),
+        error => FormatError::InvalidData(error.to_string()),
+    }
+}
@@ -1,96 +1,167 @@
 use std::borrow::Borrow;
-use std::ops::{Add, AddAssign, BitAnd, Div, Mul, Neg};
-use num_traits::Zero;
-use crate::Matrix;
+use std::ops::{Add, AddAssign, BitAnd, BitAndAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use num_traits::{Float, Zero};
+use crate::{Matrix, MatrixError};
+use crate::gemm::mul_into;
 
 
 
-//OVERLOADING + OPERATOR FOR MATRIX
-static ADDING_ERROR : &str = "Can't add two matrices with different shape !!";
+//CHECKING SHAPES
+pub(crate) fn check_shapes(left : (usize, usize), right : (usize, usize), error : fn((usize, usize), (usize, usize)) -> MatrixError) -> Result<(), MatrixError> {
+    if left == right {
+        Ok(())
+    }
+    else {
+        Err(error(left, right))
+    }
+}
 
-impl<T> Add<Matrix<T>> for Matrix<T> where T : Add<Output = T> {
-    type Output = Matrix<T>;
+impl<T> Matrix<T> {
+    pub(crate) fn check_same_shape<U>(&self, rhs : &Matrix<U>, error : fn((usize, usize), (usize, usize)) -> MatrixError) -> Result<(), MatrixError> {
+        check_shapes(self.shape(), rhs.shape(), error)
+    }
+}
 
-    fn add(self, rhs: Matrix<T>) -> Self::Output {
-        assert_eq!(self.shape(), rhs.shape(), "{}", ADDING_ERROR);
-        Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : self.nb_columns,
-            data : {
-                let data : Vec<T> = self.into_iter().zip(rhs.into_iter()).map(|(a, b)| a + b).collect();
-                data
+
+//CONVERTING CONSTANTS
+pub(crate) fn constant<T>(value : f64) -> T where T : Float { //every float type can represent the constants used in the crate
+    T::from(value).unwrap()
+}
+
+
+
+/*
+Elementwise operator between two matrices of the same shape, for every combination of owned and borrowed operands
+- $try_method : fallible version returning a MatrixError built with $error when the shapes are different
+- $element_trait : the operator on the elements
+ */
+macro_rules! elementwise_operator {
+    ($trait:ident, $method:ident, $try_method:ident, $element_trait:ident, $op:tt, $error:expr) => {
+        impl<T> Matrix<T> {
+            pub fn $try_method<M>(&self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : $element_trait<Output = T> + Copy, M : Borrow<Matrix<T>> {
+                let rhs = rhs.borrow();
+                self.check_same_shape(rhs, $error)?;
+                Ok(Matrix {
+                    nb_lines : self.nb_lines,
+                    nb_columns : self.nb_columns,
+                    data : self.data.iter().zip(&rhs.data).map(|(a, b)| *a $op *b).collect(),
+                })
             }
         }
-    }
-}
-impl<'a, T> Add<&'a Matrix<T>> for &'a Matrix<T> where T : Add<Output = T> + Copy {
-    type Output = Matrix<T>;
 
-    fn add(self, rhs: &'a Matrix<T>) -> Self::Output {
-        assert_eq!(self.shape(), rhs.shape(), "{}", ADDING_ERROR);
-        Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : self.nb_columns,
-            data : {
-                let data : Vec<T> = self.into_iter().zip(rhs.into_iter()).map(|(a, b)| *a + *b).collect();
-                data
+        impl<T> $trait<Matrix<T>> for Matrix<T> where T : $element_trait<Output = T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, rhs: Matrix<T>) -> Self::Output {
+                self.check_same_shape(&rhs, $error).unwrap_or_else(|error| panic!("{}", error));
+                Matrix {
+                    nb_lines : self.nb_lines,
+                    nb_columns : self.nb_columns,
+                    data : self.data.into_iter().zip(rhs.data).map(|(a, b)| a $op b).collect(),
+                }
             }
         }
-    }
-}
-impl<T> Add<Matrix<T>> for &Matrix<T> where T : Add<Output = T> + Copy {
-    type Output = Matrix<T>;
+        impl<'a, T> $trait<&'a Matrix<T>> for &'a Matrix<T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
 
-    fn add(self, rhs: Matrix<T>) -> Self::Output {
-        assert_eq!(self.shape(), rhs.shape(), "{}", ADDING_ERROR);
-        Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : self.nb_columns,
-            data : {
-                let data : Vec<T> = self.into_iter().zip(rhs.into_iter()).map(|(a, b)| *a + b).collect();
-                data
+            fn $method(self, rhs: &'a Matrix<T>) -> Self::Output {
+                self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
             }
         }
-    }
-}
-impl<T> Add<&Matrix<T>> for Matrix<T> where T : Add<Output = T> + Copy {
-    type Output = Matrix<T>;
+        impl<T> $trait<Matrix<T>> for &Matrix<T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
 
-    fn add(self, rhs: &Matrix<T>) -> Self::Output {
-        assert_eq!(self.shape(), rhs.shape(), "{}", ADDING_ERROR);
-        Matrix {
-            nb_lines: self.nb_lines,
-            nb_columns: self.nb_columns,
-            data: {
-                let data: Vec<T> = self.into_iter().zip(rhs.into_iter()).map(|(a, b)| a + *b).collect();
-                data
+            fn $method(self, rhs: Matrix<T>) -> Self::Output {
+                self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
             }
         }
-    }
-}
+        impl<T> $trait<&Matrix<T>> for Matrix<T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
 
+            fn $method(self, rhs: &Matrix<T>) -> Self::Output {
+                self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+            }
+        }
+    };
+}
 
+/*
+Elementwise compound assignment with an owned or a borrowed matrix, panics if the shapes are different
+ */
+macro_rules! elementwise_assign_operator {
+    ($trait:ident, $method:ident, $element_trait:ident, $op:tt, $error:expr) => {
+        #[allow(clippy::suspicious_op_assign_impl)] //&= is the Hadamard product
+        impl<T> $trait<Matrix<T>> for Matrix<T> where T : $element_trait {
+            fn $method(&mut self, rhs: Matrix<T>) {
+                self.check_same_shape(&rhs, $error).unwrap_or_else(|error| panic!("{}", error));
+                for (left, right) in self.data.iter_mut().zip(rhs.data) {
+                    *left $op right;
+                }
+            }
+        }
+        #[allow(clippy::suspicious_op_assign_impl)]
+        impl<T> $trait<&Matrix<T>> for Matrix<T> where T : $element_trait + Copy {
+            fn $method(&mut self, rhs: &Matrix<T>) {
+                self.check_same_shape(rhs, $error).unwrap_or_else(|error| panic!("{}", error));
+                for (left, right) in self.data.iter_mut().zip(&rhs.data) {
+                    *left $op *right;
+                }
+            }
+        }
+    };
+}
 
+/*
+Operator between a matrix and a scalar applied to every element, with its compound assignment
+ */
+macro_rules! scalar_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt, $assign_op:tt) => {
+        impl<T> $trait<T> for Matrix<T> where T : $trait<T, Output = T> + Copy {
+            type Output = Matrix<T>;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.map(|a| a $op rhs)
+            }
+        }
+        impl<T> $trait<T> for &Matrix<T> where T : $trait<T, Output = T> + Copy {
+            type Output = Matrix<T>;
+            fn $method(self, rhs: T) -> Self::Output {
+                Matrix {
+                    nb_lines : self.nb_lines,
+                    nb_columns : self.nb_columns,
+                    data : self.data.iter().map(|a| *a $op rhs).collect(),
+                }
+            }
+        }
+        impl<T> $assign_trait<T> for Matrix<T> where T : $assign_trait<T> + Copy {
+            fn $assign_method(&mut self, rhs: T) {
+                for a in self.data.iter_mut() {
+                    *a $assign_op rhs;
+                }
+            }
+        }
+    };
+}
 
 
 
+//OVERLOADING + AND - OPERATORS FOR MATRIX
+pub(crate) static ADDING_ERROR : &str = "Can't add two matrices with different shape !!";
+pub(crate) static SUBTRACTION_ERROR : &str = "Can't subtract two matrices with different shape !!";
 
+elementwise_operator!(Add, add, try_add, Add, +, MatrixError::Adding);
+elementwise_operator!(Sub, sub, try_sub, Sub, -, MatrixError::Subtraction);
 
 
 
-//OVERLOADING += OPERATOR
-impl<T> AddAssign<Matrix<T>> for Matrix<T> where T : AddAssign {
-    fn add_assign(&mut self, rhs: Self) {
-        if self.nb_lines == rhs.nb_lines && self.nb_columns == rhs.nb_columns {
-            for (right, left) in self.into_iter().zip(rhs.into_iter()) {
-                *right += left;
-            }
-        }
-    }
-}
 
 
 
+//OVERLOADING +=, -=, &=, /= AND %= OPERATORS
+elementwise_assign_operator!(AddAssign, add_assign, AddAssign, +=, MatrixError::Adding);
+elementwise_assign_operator!(SubAssign, sub_assign, SubAssign, -=, MatrixError::Subtraction);
+elementwise_assign_operator!(BitAndAssign, bitand_assign, MulAssign, *=, MatrixError::HadamardProduct);
+elementwise_assign_operator!(DivAssign, div_assign, DivAssign, /=, MatrixError::Division);
+elementwise_assign_operator!(RemAssign, rem_assign, RemAssign, %=, MatrixError::Remainder);
 
 
 
@@ -99,26 +170,38 @@ impl<T> AddAssign<Matrix<T>> for Matrix<T> where T : AddAssign {
 
 //ADDING ALONG COLUMNS OR LINES
 impl<T> Matrix<T> where T : AddAssign + Copy {
-    pub fn add_to_lines<M>(mut self, rhs : M) -> Matrix<T> where M : Borrow<Matrix<T>> {
+    pub fn try_add_to_lines<M>(mut self, rhs : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
         let matrix_line = rhs.borrow();
-        assert_eq!(matrix_line.nb_lines, 1, "The matrix to add must have exactly one line");
-        assert_eq!(self.nb_columns, matrix_line.nb_columns, "Both matrix must have the same number of columns");
-        for i in 0..self.size() {
-            self.data[i] += matrix_line.data[i % self.nb_columns];
+        if matrix_line.nb_lines != 1 || self.nb_columns != matrix_line.nb_columns {
+            return Err(MatrixError::AddToLines(self.shape(), matrix_line.shape()))
+        }
+        for line in self.rows_mut() {
+            for (a, b) in line.iter_mut().zip(&matrix_line.data) {
+                *a += *b;
+            }
         }
-        self
+        Ok(self)
+    }
+
+    pub fn add_to_lines<M>(self, rhs : M) -> Matrix<T> where M : Borrow<Matrix<T>> {
+        self.try_add_to_lines(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 
-    pub fn add_to_columns<M>(mut self, rhs : M) -> Matrix<T> where M : Borrow<Matrix<T>> {
+    pub fn try_add_to_columns<M>(mut self, rhs : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> {
         let matrix_column = rhs.borrow();
-        assert_eq!(matrix_column.nb_columns, 1, "The matrix to add must have exactly one column");
-        assert_eq!(self.nb_lines, matrix_column.nb_lines, "Both matrix must have the same number of lines");
-        for i in 0..self.nb_lines {
-            for j in 0..self.nb_columns {
-                self.data[i * self.nb_columns + j] += matrix_column.data[i];
+        if matrix_column.nb_columns != 1 || self.nb_lines != matrix_column.nb_lines {
+            return Err(MatrixError::AddToColumns(self.shape(), matrix_column.shape()))
+        }
+        for (line, &b) in self.rows_mut().zip(&matrix_column.data) {
+            for a in line {
+                *a += b;
             }
         }
-        self
+        Ok(self)
+    }
+
+    pub fn add_to_columns<M>(self, rhs : M) -> Matrix<T> where M : Borrow<Matrix<T>> {
+        self.try_add_to_columns(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
@@ -143,110 +226,54 @@ impl<T> Neg for Matrix<T> where T : Neg<Output = T> {
 
 
 //OVERLOADING * OPERATOR FOR MATRIX
-static MULTIPLICATION_ERROR : &str = "Can't multiply matrices with incompatible shapes";
+pub(crate) static MULTIPLICATION_ERROR : &str = "Can't multiply matrices with incompatible shapes";
 
-impl<T> Mul<Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Copy {
+impl<T> Matrix<T> {
+    pub fn try_mul<M>(&self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy, M : Borrow<Matrix<T>> {
+        let mut res = Matrix::new();
+        mul_into(self, rhs.borrow(), &mut res)?;
+        Ok(res)
+    }
+}
+
+impl<T> Mul<Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: Matrix<T>) -> Self::Output {
-        assert_eq!(self.nb_columns, rhs.nb_lines, "{}", MULTIPLICATION_ERROR);
-        let size = self.nb_lines * rhs.nb_columns;
-        let mut res = Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : rhs.nb_columns,
-            data : {
-                let mut temp = Vec::with_capacity(size);
-                unsafe {temp.set_len(size)}
-                temp
-            }
-        };
-        for i in 0..self.nb_lines {
-            for j in 0..rhs.nb_columns {
-                res [i][j] = self[i][0] * rhs[0][j];
-                for k in 1..self.nb_columns {
-                    res[i][j] += self[i][k] * rhs[k][j];
-                }
-            }
-        }
-        res
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
-impl<'a, T> Mul<&'a Matrix<T>> for &'a Matrix<T> where T : Mul<Output = T> + AddAssign + Copy {
+impl<'a, T> Mul<&'a Matrix<T>> for &'a Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: &'a Matrix<T>) -> Self::Output {
-        assert_eq!(self.nb_columns, rhs.nb_lines, "{}", MULTIPLICATION_ERROR);
-        let size = self.nb_lines * rhs.nb_columns;
-        let mut res = Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : rhs.nb_columns,
-            data : {
-                let mut temp = Vec::with_capacity(size);
-                unsafe {temp.set_len(size)}
-                temp
-            }
-        };
-        for i in 0..self.nb_lines {
-            for j in 0..rhs.nb_columns {
-                res [i][j] = self[i][0] * rhs[0][j];
-                for k in 1..self.nb_columns {
-                    res[i][j] += self[i][k] * rhs[k][j];
-                }
-            }
-        }
-        res
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
-impl<T> Mul<Matrix<T>> for &Matrix<T> where T : Mul<Output = T> + AddAssign + Copy {
+impl<T> Mul<Matrix<T>> for &Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: Matrix<T>) -> Self::Output {
-        assert_eq!(self.nb_columns, rhs.nb_lines, "{}", MULTIPLICATION_ERROR);
-        let size = self.nb_lines * rhs.nb_columns;
-        let mut res = Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : rhs.nb_columns,
-            data : {
-                let mut temp = Vec::with_capacity(size);
-                unsafe {temp.set_len(size)}
-                temp
-            }
-        };
-        for i in 0..self.nb_lines {
-            for j in 0..rhs.nb_columns {
-                res [i][j] = self[i][0] * rhs[0][j];
-                for k in 1..self.nb_columns {
-                    res[i][j] += self[i][k] * rhs[k][j];
-                }
-            }
-        }
-        res
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
-impl<T> Mul<&Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Copy {
+impl<T> Mul<&Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
     type Output = Matrix<T>;
 
     fn mul(self, rhs: &Matrix<T>) -> Self::Output {
-        assert_eq!(self.nb_columns, rhs.nb_lines, "{}", MULTIPLICATION_ERROR);
-        let size = self.nb_lines * rhs.nb_columns;
-        let mut res = Matrix {
-            nb_lines : self.nb_lines,
-            nb_columns : rhs.nb_columns,
-            data : {
-                let mut temp = Vec::with_capacity(size);
-                unsafe {temp.set_len(size)}
-                temp
-            }
-        };
-        for i in 0..self.nb_lines {
-            for j in 0..rhs.nb_columns {
-                res [i][j] = self[i][0] * rhs[0][j];
-                for k in 1..self.nb_columns {
-                    res[i][j] += self[i][k] * rhs[k][j];
-                }
-            }
-        }
-        res
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+//OVERLOADING *= OPERATOR FOR MATRIX, the matrix takes the shape of the product
+impl<T> MulAssign<Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    fn mul_assign(&mut self, rhs: Matrix<T>) {
+        *self = self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error));
+    }
+}
+impl<T> MulAssign<&Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    fn mul_assign(&mut self, rhs: &Matrix<T>) {
+        *self = self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
@@ -255,64 +282,129 @@ impl<T> Mul<&Matrix<T>> for Matrix<T> where T : Mul<Output = T> + AddAssign + Co
 
 
 
-//OVERLOADING * OPERATOR FOR SCALARS
-impl<T> Mul<T> for Matrix<T> where T : Mul<T, Output = T> + Copy {
-    type Output = Matrix<T>;
-    fn mul(self, rhs: T) -> Self::Output {
-        self.map(|a| a * rhs)
-    }
+//OVERLOADING *, / AND % OPERATORS FOR SCALARS
+scalar_operator!(Mul, mul, MulAssign, mul_assign, *, *=);
+scalar_operator!(Div, div, DivAssign, div_assign, /, /=);
+scalar_operator!(Rem, rem, RemAssign, rem_assign, %, %=);
+
+/*
+Scalar on the left of +, -, *, / and %, implemented for the primitive types since a generic T can't be used as left operand
+The scalar stays the left operand of each elementwise operation : 1. - m computes 1 - m[i][j]
+ */
+macro_rules! scalar_on_left {
+    ($($t:ty),*) => {
+        $(
+            scalar_on_left!(@operators $t, Add add +, Sub sub -, Mul mul *, Div div /, Rem rem %);
+        )*
+    };
+    (@operators $t:ty, $($trait:ident $method:ident $op:tt),*) => {
+        $(
+            impl $trait<Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+                fn $method(self, rhs: Matrix<$t>) -> Self::Output {
+                    rhs.map(|x| self $op x)
+                }
+            }
+            impl $trait<&Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+                fn $method(self, rhs: &Matrix<$t>) -> Self::Output {
+                    rhs.map_ref(|x| self $op *x)
+                }
+            }
+        )*
+    };
 }
+scalar_on_left!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
 
 
 
 
 
 
-//OVERLOADING / OPERATOR FOR SCALARS
-impl<T> Div<T> for Matrix<T> where T : Div<T, Output = T> + Copy {
-    type Output = Matrix<T>;
-    fn div(self, rhs: T) -> Self::Output {
-        self.map(|a| a / rhs)
-    }
-}
+//OVERLOADING & OPERATOR for the Hadamard product
+pub(crate) static HADAMAR_PRODUCT_ERROR : &str = "Can't make the Hadamard product of matrices with different shapes";
 
+elementwise_operator!(BitAnd, bitand, try_hadamard, Mul, *, MatrixError::HadamardProduct);
 
 
 
 
 
 
-//OVERLOADING & OPERATOR for the Hadamard product
-static HADAMAR_PRODUCT_ERROR : &str = "Can't make the Hadamard product of matrices with different shapes";
+//OVERLOADING / AND % OPERATORS for the elementwise division and remainder
+pub(crate) static DIVISION_ERROR : &str = "Can't divide elementwise matrices with different shapes";
+pub(crate) static REMAINDER_ERROR : &str = "Can't compute the elementwise remainder of matrices with different shapes";
 
-impl<T> BitAnd for Matrix<T> where T : Mul<Output = T>  {
-    type Output = Matrix<T>;
+elementwise_operator!(Div, div, try_div, Div, /, MatrixError::Division);
+elementwise_operator!(Rem, rem, try_rem, Rem, %, MatrixError::Remainder);
 
-    fn bitand(self, rhs: Self) -> Self::Output {
-        assert_eq!(self.shape(), rhs.shape(), "{}", HADAMAR_PRODUCT_ERROR);
+//FUNCTIONALITIES
+impl<T> Matrix<T> {
+    pub fn map<F>(self, f: F) -> Matrix<T> where F: Fn(T) -> T { //Apply a function to the Matrix
         Matrix {
             nb_lines : self.nb_lines,
             nb_columns : self.nb_columns,
-            data : {
-                let data : Vec<T> = self.into_iter().zip(rhs.into_iter()).map(|(a, b)| a * b).collect();
-                data
-            }
+            data : self.data.into_iter().map(f).collect(),
         }
     }
-}
 
-//FUNCTIONALITIES
-impl<T> Matrix<T> {
-    pub fn map<F>(self, f: F) -> Matrix<T> where F: Fn(T) -> T { //Apply a function to the Matrix
+    pub fn map_to<U, F>(self, f : F) -> Matrix<U> where F : FnMut(T) -> U { //Apply a function changing the type of the elements
         Matrix {
             nb_lines : self.nb_lines,
             nb_columns : self.nb_columns,
-            data : {
-                let data : Vec<T> = self.into_iter().map(f).collect();
-                data
-            }
+            data : self.data.into_iter().map(f).collect(),
         }
     }
+
+    pub fn map_ref<U, F>(&self, f : F) -> Matrix<U> where F : FnMut(&T) -> U { //Same as map_to without consuming the Matrix
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.data.iter().map(f).collect(),
+        }
+    }
+
+    pub fn map_inplace<F>(&mut self, f : F) where F : FnMut(&mut T) { //Modify every element without allocating
+        self.data.iter_mut().for_each(f);
+    }
+
+    pub fn map_indexed<U, F>(&self, mut f : F) -> Matrix<U> where F : FnMut(usize, usize, &T) -> U { //f receives the line, the column and the element
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.indexed_iter().map(|((i, j), x)| f(i, j, x)).collect(),
+        }
+    }
+
+    /*
+    Combine the elements at the same position in both matrices, which must have the same shape
+     */
+    pub fn try_zip_with<U, V, F>(&self, other : &Matrix<U>, mut f : F) -> Result<Matrix<V>, MatrixError> where F : FnMut(&T, &U) -> V {
+        self.check_same_shape(other, MatrixError::Zipping)?;
+        Ok(Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.data.iter().zip(&other.data).map(|(a, b)| f(a, b)).collect(),
+        })
+    }
+
+    pub fn zip_with<U, V, F>(&self, other : &Matrix<U>, f : F) -> Matrix<V> where F : FnMut(&T, &U) -> V {
+        self.try_zip_with(other, f).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_zip3_with<U, V, W, F>(&self, second : &Matrix<U>, third : &Matrix<V>, mut f : F) -> Result<Matrix<W>, MatrixError> where F : FnMut(&T, &U, &V) -> W {
+        self.check_same_shape(second, MatrixError::Zipping)?;
+        self.check_same_shape(third, MatrixError::Zipping)?;
+        Ok(Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.data.iter().zip(&second.data).zip(&third.data).map(|((a, b), c)| f(a, b, c)).collect(),
+        })
+    }
+
+    pub fn zip3_with<U, V, W, F>(&self, second : &Matrix<U>, third : &Matrix<V>, f : F) -> Matrix<W> where F : FnMut(&T, &U, &V) -> W {
+        self.try_zip3_with(second, third, f).unwrap_or_else(|error| panic!("{}", error))
+    }
 }
 
 impl<T> Matrix<T> where T : AddAssign + Clone + Zero {
@@ -322,24 +414,25 @@ impl<T> Matrix<T> where T : AddAssign + Clone + Zero {
             nb_columns,
             nb_lines: 1,
             data: {
-                let mut data: Vec<T> = vec![num_traits::zero(); self.nb_columns];
-                for (i, value) in (0..self.size()).zip(self.into_iter()) {
+                let mut data: Vec<T> = vec![num_traits::zero(); nb_columns];
+                for (i, value) in (0..self.size()).zip(self) {
                     data[i % nb_columns] += value;
                 }
                 data
-            }
+            },
         }
     }
 
     pub fn sum_col(self) -> Matrix<T> {
         let nb_lines = self.nb_lines;
+        let nb_columns = self.nb_columns;
         Matrix {
             nb_lines,
             nb_columns : 1,
             data: {
-                let mut data: Vec<T> = vec![num_traits::zero(); self.nb_columns];
-                for (i, value) in (0..self.size()).zip(self.into_iter()) {
-                    data[i / nb_lines] += value;
+                let mut data: Vec<T> = vec![num_traits::zero(); nb_lines];
+                for (i, value) in (0..self.size()).zip(self) {
+                    data[i / nb_columns] += value;
                 }
                 data
             }
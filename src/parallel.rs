@@ -0,0 +1,169 @@
+use std::borrow::Borrow;
+use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub};
+use num_traits::Zero;
+use rayon::prelude::*;
+use crate::{Matrix, MatrixError};
+use crate::gemm::par_multiply_add;
+
+
+/*
+Under this number of elements the par_* methods stay on the current thread, splitting the work would cost more than it saves
+ */
+pub const PARALLEL_THRESHOLD : usize = 1 << 15;
+
+/*
+The par_* methods split the work across the rayon threads by blocks of lines and require Send + Sync elements
+The operators and the other methods always run on the current thread, so enabling the feature doesn't change their bounds
+ */
+
+
+//HELPERS CHOOSING BETWEEN THE SERIAL AND THE PARALLEL PATHS
+fn zip_map<T, U, F>(a : &[T], b : &[T], f : F) -> Vec<U> where T : Sync, U : Send, F : Fn(&T, &T) -> U + Send + Sync {
+    if a.len() >= PARALLEL_THRESHOLD {
+        return a.par_iter().zip(b).map(|(a, b)| f(a, b)).collect()
+    }
+    a.iter().zip(b).map(|(a, b)| f(a, b)).collect()
+}
+
+/*
+Call f(i, line) on every line of the data of a matrix with nb_columns columns
+ */
+fn for_each_line_mut<T, F>(data : &mut [T], nb_columns : usize, f : F) where T : Send, F : Fn(usize, &mut [T]) + Send + Sync {
+    if nb_columns == 0 {
+        return
+    }
+    if data.len() >= PARALLEL_THRESHOLD {
+        data.par_chunks_mut(nb_columns).enumerate().for_each(|(i, line)| f(i, line));
+        return
+    }
+    data.chunks_mut(nb_columns).enumerate().for_each(|(i, line)| f(i, line));
+}
+
+/*
+Fold the lines of the data of a matrix by blocks then reduce the partial results, the blocks are processed in parallel
+ */
+fn fold_lines<T, A, I, F, R>(data : &[T], nb_columns : usize, init : I, fold : F, reduce : R) -> A where T : Sync, A : Send, I : Fn() -> A + Send + Sync, F : Fn(A, &[T]) -> A + Send + Sync, R : Fn(A, A) -> A + Send + Sync {
+    if nb_columns == 0 {
+        return init()
+    }
+    if data.len() >= PARALLEL_THRESHOLD {
+        let lines_per_block = (PARALLEL_THRESHOLD / nb_columns).max(1);
+        return data.par_chunks(lines_per_block * nb_columns)
+            .map(|block| block.chunks(nb_columns).fold(init(), &fold))
+            .reduce(&init, &reduce)
+    }
+    data.chunks(nb_columns).fold(init(), fold)
+}
+
+
+/*
+Parallel counterpart of an elementwise operator between two matrices of the same shape
+ */
+macro_rules! par_elementwise_operator {
+    ($par_method:ident, $try_par_method:ident, $element_trait:ident, $op:tt, $error:expr) => {
+        impl<T> Matrix<T> where T : Send + Sync {
+            pub fn $try_par_method<M>(&self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : $element_trait<Output = T> + Copy, M : Borrow<Matrix<T>> {
+                let rhs = rhs.borrow();
+                self.check_same_shape(rhs, $error)?;
+                Ok(Matrix {
+                    nb_lines : self.nb_lines,
+                    nb_columns : self.nb_columns,
+                    data : zip_map(&self.data, &rhs.data, |a, b| *a $op *b),
+                })
+            }
+
+            pub fn $par_method<M>(&self, rhs : M) -> Matrix<T> where T : $element_trait<Output = T> + Copy, M : Borrow<Matrix<T>> {
+                self.$try_par_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+            }
+        }
+    };
+}
+
+par_elementwise_operator!(par_add, try_par_add, Add, +, MatrixError::Adding);
+par_elementwise_operator!(par_sub, try_par_sub, Sub, -, MatrixError::Subtraction);
+par_elementwise_operator!(par_hadamard, try_par_hadamard, Mul, *, MatrixError::HadamardProduct);
+par_elementwise_operator!(par_div, try_par_div, Div, /, MatrixError::Division);
+par_elementwise_operator!(par_rem, try_par_rem, Rem, %, MatrixError::Remainder);
+
+
+//PARALLEL FUNCTIONALITIES
+impl<T> Matrix<T> where T : Send + Sync {
+    pub fn par_map<F>(self, f : F) -> Matrix<T> where F : Fn(T) -> T + Send + Sync { //Apply a function to the Matrix on every thread, whatever its size
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.data.into_par_iter().map(f).collect(),
+        }
+    }
+
+    pub fn par_rows_mut(&mut self) -> rayon::slice::ChunksMut<'_, T> { //parallel iterator over the lines of the Matrix
+        self.data.par_chunks_mut(self.nb_columns.max(1))
+    }
+
+    pub fn try_par_mul<M>(&self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy, M : Borrow<Matrix<T>> {
+        let rhs = rhs.borrow();
+        if self.nb_columns != rhs.nb_lines {
+            return Err(MatrixError::Multiplication(self.shape(), rhs.shape()))
+        }
+        let mut res = Matrix::zeros(self.nb_lines, rhs.nb_columns);
+        par_multiply_add(None, self.as_view(), rhs.as_view(), &mut res.data);
+        Ok(res)
+    }
+
+    pub fn par_mul<M>(&self, rhs : M) -> Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy, M : Borrow<Matrix<T>> {
+        self.try_par_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_par_add_to_lines<M>(mut self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : AddAssign + Copy, M : Borrow<Matrix<T>> {
+        let matrix_line = rhs.borrow();
+        if matrix_line.nb_lines != 1 || self.nb_columns != matrix_line.nb_columns {
+            return Err(MatrixError::AddToLines(self.shape(), matrix_line.shape()))
+        }
+        for_each_line_mut(&mut self.data, self.nb_columns, |_, line| {
+            for (a, b) in line.iter_mut().zip(&matrix_line.data) {
+                *a += *b;
+            }
+        });
+        Ok(self)
+    }
+
+    pub fn par_add_to_lines<M>(self, rhs : M) -> Matrix<T> where T : AddAssign + Copy, M : Borrow<Matrix<T>> {
+        self.try_par_add_to_lines(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_par_add_to_columns<M>(mut self, rhs : M) -> Result<Matrix<T>, MatrixError> where T : AddAssign + Copy, M : Borrow<Matrix<T>> {
+        let matrix_column = rhs.borrow();
+        if matrix_column.nb_columns != 1 || self.nb_lines != matrix_column.nb_lines {
+            return Err(MatrixError::AddToColumns(self.shape(), matrix_column.shape()))
+        }
+        for_each_line_mut(&mut self.data, self.nb_columns, |i, line| {
+            for a in line {
+                *a += matrix_column.data[i];
+            }
+        });
+        Ok(self)
+    }
+
+    pub fn par_add_to_columns<M>(self, rhs : M) -> Matrix<T> where T : AddAssign + Copy, M : Borrow<Matrix<T>> {
+        self.try_par_add_to_columns(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn par_sum_line(self) -> Matrix<T> where T : AddAssign + Clone + Zero {
+        let nb_columns = self.nb_columns;
+        Matrix {
+            nb_columns,
+            nb_lines: 1,
+            data: fold_lines(&self.data, nb_columns, || vec![num_traits::zero(); nb_columns], |mut data : Vec<T>, line| {
+                for (sum, value) in data.iter_mut().zip(line) {
+                    *sum += value.clone();
+                }
+                data
+            }, |mut data, other| {
+                for (sum, value) in data.iter_mut().zip(other) {
+                    *sum += value;
+                }
+                data
+            }),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::borrow::Borrow;
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+
+
+/*
+Householder QR factorization : A * P = Q * R
+- qr : Matrix<T>                R in the upper part, the Householder vectors (unit first component implied) under the diagonal
+- tau : Vec<T>                  scaling of each Householder reflection H_k = I - tau_k * v_k * v_k^T
+- permutation : Vec<usize>      column j of A * P is column permutation[j] of A (identity when not pivoted)
+- pivoted : bool                whether the columns were pivoted, the diagonal of R is then decreasing in absolute value
+ */
+#[derive(Debug, Clone)]
+pub struct QR<T> {
+    qr : Matrix<T>,
+    tau : Vec<T>,
+    permutation : Vec<usize>,
+    pivoted : bool,
+}
+
+/*
+Result of a least-squares resolution of A * X = B
+- solution : Matrix<T>          the minimizer X, one column per right-hand side
+- residuals : Matrix<T>         line matrix containing the squared norm of A * X - B for each right-hand side
+- rank : usize                  the numerical rank of A
+ */
+#[derive(Debug, Clone)]
+pub struct LeastSquares<T> {
+    pub solution : Matrix<T>,
+    pub residuals : Matrix<T>,
+    pub rank : usize,
+}
+
+
+//METHODS TO CREATE THE FACTORIZATION
+impl<T> Matrix<T> where T : Float {
+    pub fn qr(&self) -> QR<T> {
+        QR::factorize(self.clone(), false)
+    }
+
+    pub fn qr_pivoted(&self) -> QR<T> { //at each step the remaining column with the greatest norm is chosen as pivot
+        QR::factorize(self.clone(), true)
+    }
+}
+
+impl<T> QR<T> where T : Float {
+    fn factorize(mut qr : Matrix<T>, pivoting : bool) -> QR<T> {
+        let (m, n) = qr.shape();
+        let steps = m.min(n);
+        let mut tau = Vec::with_capacity(steps);
+        let mut permutation : Vec<usize> = (0..n).collect();
+        for k in 0..steps {
+            if pivoting {
+                let norm = |j : usize| (k..m).fold(T::zero(), |sum, i| sum + qr[i][j] * qr[i][j]);
+                let mut pivot = k;
+                let mut pivot_norm = norm(k);
+                for j in k + 1..n {
+                    let column_norm = norm(j);
+                    if column_norm > pivot_norm {
+                        pivot = j;
+                        pivot_norm = column_norm;
+                    }
+                }
+                if pivot != k {
+                    for i in 0..m {
+                        qr.data.swap(i * n + k, i * n + pivot);
+                    }
+                    permutation.swap(k, pivot);
+                }
+            }
+
+            let x0 = qr[k][k];
+            let tail_norm = (k + 1..m).fold(T::zero(), |sum, i| sum + qr[i][k] * qr[i][k]);
+            if tail_norm == T::zero() { //nothing to eliminate, H_k is the identity
+                tau.push(T::zero());
+                continue
+            }
+            let norm = (x0 * x0 + tail_norm).sqrt();
+            let beta = if x0 >= T::zero() { -norm } else { norm };
+            let scale = x0 - beta;
+            for i in k + 1..m {
+                qr[i][k] = qr[i][k] / scale;
+            }
+            let tau_k = (beta - x0) / beta;
+            qr[k][k] = beta;
+            for j in k + 1..n {
+                let mut w = qr[k][j];
+                for i in k + 1..m {
+                    w = w + qr[i][k] * qr[i][j];
+                }
+                w = w * tau_k;
+                qr[k][j] = qr[k][j] - w;
+                for i in k + 1..m {
+                    let v = qr[i][k];
+                    qr[i][j] = qr[i][j] - w * v;
+                }
+            }
+            tau.push(tau_k);
+        }
+        QR {
+            qr,
+            tau,
+            permutation,
+            pivoted : pivoting,
+        }
+    }
+
+    fn apply_reflection(&self, k : usize, x : &mut Matrix<T>) { //x = H_k * x
+        let m = self.qr.nb_lines;
+        for c in 0..x.nb_columns {
+            let mut w = x[k][c];
+            for i in k + 1..m {
+                w = w + self.qr[i][k] * x[i][c];
+            }
+            w = w * self.tau[k];
+            x[k][c] = x[k][c] - w;
+            for i in k + 1..m {
+                x[i][c] = x[i][c] - w * self.qr[i][k];
+            }
+        }
+    }
+}
+
+
+//GETTERS
+impl<T> QR<T> where T : Float {
+    pub fn thin_q(&self) -> Matrix<T> { //return the m x min(m, n) matrix with orthonormal columns
+        self.q(self.tau.len())
+    }
+
+    pub fn full_q(&self) -> Matrix<T> { //return the m x m orthogonal matrix
+        self.q(self.qr.nb_lines)
+    }
+
+    fn q(&self, nb_columns : usize) -> Matrix<T> {
+        let m = self.qr.nb_lines;
+        let mut q = Matrix::zeros(m, nb_columns);
+        for i in 0..nb_columns {
+            q[i][i] = T::one();
+        }
+        for k in (0..self.tau.len()).rev() {
+            self.apply_reflection(k, &mut q);
+        }
+        q
+    }
+
+    pub fn thin_r(&self) -> Matrix<T> { //return the min(m, n) x n upper triangular matrix
+        self.r(self.tau.len())
+    }
+
+    pub fn full_r(&self) -> Matrix<T> { //return the m x n upper triangular matrix
+        self.r(self.qr.nb_lines)
+    }
+
+    fn r(&self, nb_lines : usize) -> Matrix<T> {
+        let n = self.qr.nb_columns;
+        let mut r = Matrix::zeros(nb_lines, n);
+        for i in 0..nb_lines.min(self.tau.len()) {
+            r[i][i..].copy_from_slice(&self.qr[i][i..]);
+        }
+        r
+    }
+
+    pub fn p(&self) -> Matrix<T> { //return the column permutation matrix
+        let n = self.qr.nb_columns;
+        let mut p = Matrix::zeros(n, n);
+        for (j, &i) in self.permutation.iter().enumerate() {
+            p[i][j] = T::one();
+        }
+        p
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+}
+
+
+//FUNCTIONALITIES
+impl<T> QR<T> where T : Float {
+    /*
+    Return the number of diagonal values of R greater than the tolerance
+    Only a reliable rank estimate for a pivoted factorization, without pivoting a small diagonal value doesn't mean the
+    following columns are dependent (use qr_pivoted or svd to estimate the rank)
+     */
+    pub fn rank(&self, tolerance : T) -> usize {
+        (0..self.tau.len()).filter(|&k| self.qr[k][k].abs() > tolerance).count()
+    }
+
+    pub fn qt_mul<M>(&self, b : M) -> Result<Matrix<T>, MatrixError> where M : Borrow<Matrix<T>> { //return Q^T * b
+        let b = b.borrow();
+        if b.nb_lines != self.qr.nb_lines {
+            return Err(MatrixError::Solving(self.qr.shape(), b.shape()))
+        }
+        let mut x = b.clone();
+        for k in 0..self.tau.len() {
+            self.apply_reflection(k, &mut x);
+        }
+        Ok(x)
+    }
+
+    /*
+    Compute the basic least-squares solution of A * X = B, the columns of R beyond the numerical rank are ignored
+    - b : Matrix<T>             the right-hand sides, one per column
+    - tolerance : T             diagonal values of R lower or equal to it are considered null
+    Without pivoting the null diagonal values of R can be anywhere, so MatrixError::Singular is returned
+    if one of them is lower or equal to the tolerance, use qr_pivoted for rank-deficient matrices
+     */
+    pub fn lstsq_with_tolerance<M>(&self, b : M, tolerance : T) -> Result<LeastSquares<T>, MatrixError> where M : Borrow<Matrix<T>> {
+        let qtb = self.qt_mul(b)?;
+        let (m, n) = self.qr.shape();
+        let rank = self.rank(tolerance);
+        if !self.pivoted && rank < self.tau.len() {
+            return Err(MatrixError::Singular)
+        }
+        let mut solution = Matrix::zeros(n, qtb.nb_columns);
+        let mut residuals = Matrix::zeros(1, qtb.nb_columns);
+        for c in 0..qtb.nb_columns {
+            let mut z = vec![T::zero(); rank];
+            for i in (0..rank).rev() {
+                let mut value = qtb[i][c];
+                for (k, z_k) in z.iter().enumerate().skip(i + 1) {
+                    value = value - self.qr[i][k] * *z_k;
+                }
+                z[i] = value / self.qr[i][i];
+            }
+            for (j, value) in z.into_iter().enumerate() {
+                solution[self.permutation[j]][c] = value;
+            }
+            residuals[0][c] = (rank..m).fold(T::zero(), |sum, i| sum + qtb[i][c] * qtb[i][c]);
+        }
+        Ok(LeastSquares {
+            solution,
+            residuals,
+            rank,
+        })
+    }
+
+    pub fn lstsq<M>(&self, b : M) -> Result<LeastSquares<T>, MatrixError> where M : Borrow<Matrix<T>> { //the default tolerance is epsilon * max(m, n) * max|r_kk|
+        let (m, n) = self.qr.shape();
+        let largest = (0..self.tau.len()).fold(T::zero(), |max, k| max.max(self.qr[k][k].abs()));
+        let tolerance = T::epsilon() * T::from(m.max(n)).unwrap_or_else(T::one) * largest;
+        self.lstsq_with_tolerance(b, tolerance)
+    }
+}
+
+
+//SHORTCUTS ON MATRIX
+impl<T> Matrix<T> where T : Float {
+    pub fn lstsq<M>(&self, b : M) -> Result<LeastSquares<T>, MatrixError> where M : Borrow<Matrix<T>> { //use a column-pivoted factorization to handle rank-deficient matrices
+        self.qr_pivoted().lstsq(b)
+    }
+}
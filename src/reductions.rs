@@ -0,0 +1,171 @@
+use std::ops::{AddAssign, MulAssign};
+use num_traits::{Float, One, Zero};
+use crate::{Matrix, MatrixError};
+use crate::operations::constant;
+
+
+/*
+Axis along which a reduction is applied
+- Rows : each line is reduced, the result is a nb_lines x 1 column
+- Columns : each column is reduced, the result is a 1 x nb_columns line
+- All : every element is reduced, the result is a 1 x 1 matrix
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Rows,
+    Columns,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Norm {
+    L1, //sum of the absolute values
+    L2, //square root of the sum of the squares
+    Inf, //greatest absolute value
+}
+
+
+impl<T> Matrix<T> {
+    /*
+    Generic reduction, fold is called with the accumulator, the position of the element along the reduced axis
+    (the column for Rows, the line for Columns, the index in the data for All) and the element
+    finish is called with the accumulator and the number of reduced elements
+     */
+    fn reduce<A, U, F, G>(&self, axis : Axis, init : A, fold : F, finish : G) -> Matrix<U> where A : Copy, F : Fn(A, usize, &T) -> A, G : Fn(A, usize) -> U {
+        match axis {
+            Axis::Rows => Matrix {
+                nb_lines : self.nb_lines,
+                nb_columns : 1,
+                data : self.rows().map(|line| finish(line.iter().enumerate().fold(init, |a, (j, x)| fold(a, j, x)), self.nb_columns)).collect(),
+            },
+            Axis::Columns => {
+                let mut accumulators = vec![init; self.nb_columns];
+                for (i, line) in self.rows().enumerate() {
+                    for (a, x) in accumulators.iter_mut().zip(line) {
+                        *a = fold(*a, i, x);
+                    }
+                }
+                Matrix {
+                    nb_lines : 1,
+                    nb_columns : self.nb_columns,
+                    data : accumulators.into_iter().map(|a| finish(a, self.nb_lines)).collect(),
+                }
+            }
+            Axis::All => Matrix {
+                nb_lines : 1,
+                nb_columns : 1,
+                data : vec![finish(self.data.iter().enumerate().fold(init, |a, (k, x)| fold(a, k, x)), self.size())],
+            },
+        }
+    }
+
+    /*
+    Position and value of the element selected by better(candidate, current) along the axis
+    The axis reduced must not be empty
+     */
+    fn select_along<F>(&self, axis : Axis, better : F) -> Result<Matrix<(usize, T)>, MatrixError> where T : Copy, F : Fn(&T, &T) -> bool {
+        let empty = match axis {
+            Axis::Rows => self.nb_columns == 0,
+            Axis::Columns => self.nb_lines == 0,
+            Axis::All => self.size() == 0,
+        };
+        if empty {
+            return Err(MatrixError::EmptyReduction(self.shape()))
+        }
+        Ok(self.reduce(axis, None, |best : Option<(usize, T)>, k, x| match best {
+            Some((_, value)) if !better(x, &value) => best,
+            _ => Some((k, *x)),
+        }, |best, _| best.unwrap()))
+    }
+}
+
+
+//SUM, MEAN AND PRODUCT
+impl<T> Matrix<T> where T : Copy {
+    pub fn sum(&self, axis : Axis) -> Matrix<T> where T : Zero + AddAssign {
+        self.reduce(axis, T::zero(), |mut sum, _, x| {
+            sum += *x;
+            sum
+        }, |sum, _| sum)
+    }
+
+    pub fn mean(&self, axis : Axis) -> Matrix<T> where T : Float + AddAssign { //NaN for an empty axis
+        self.reduce(axis, T::zero(), |mut sum, _, x| {
+            sum += *x;
+            sum
+        }, |sum, n| sum / constant(n as f64))
+    }
+
+    pub fn prod(&self, axis : Axis) -> Matrix<T> where T : One + MulAssign {
+        self.reduce(axis, T::one(), |mut product, _, x| {
+            product *= *x;
+            product
+        }, |product, _| product)
+    }
+}
+
+
+//MINIMUM, MAXIMUM AND THEIR POSITIONS
+/*
+The first position is kept when several elements are equal, the comparisons with NaN are false so NaN is never selected
+unless it is the first element
+The positions are the columns for Axis::Rows, the lines for Axis::Columns and the index in the data (i * nb_columns + j) for Axis::All
+ */
+impl<T> Matrix<T> where T : PartialOrd + Copy {
+    pub fn try_min(&self, axis : Axis) -> Result<Matrix<T>, MatrixError> {
+        Ok(self.select_along(axis, |x, min| x < min)?.map_to(|(_, min)| min))
+    }
+
+    pub fn min(&self, axis : Axis) -> Matrix<T> {
+        self.try_min(axis).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_max(&self, axis : Axis) -> Result<Matrix<T>, MatrixError> {
+        Ok(self.select_along(axis, |x, max| x > max)?.map_to(|(_, max)| max))
+    }
+
+    pub fn max(&self, axis : Axis) -> Matrix<T> {
+        self.try_max(axis).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_argmin(&self, axis : Axis) -> Result<Matrix<usize>, MatrixError> {
+        Ok(self.select_along(axis, |x, min| x < min)?.map_to(|(k, _)| k))
+    }
+
+    pub fn argmin(&self, axis : Axis) -> Matrix<usize> {
+        self.try_argmin(axis).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_argmax(&self, axis : Axis) -> Result<Matrix<usize>, MatrixError> {
+        Ok(self.select_along(axis, |x, max| x > max)?.map_to(|(k, _)| k))
+    }
+
+    pub fn argmax(&self, axis : Axis) -> Matrix<usize> { //with one sample by line, argmax(Axis::Rows) gives the predicted classes
+        self.try_argmax(axis).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+
+//NORMS
+impl<T> Matrix<T> where T : Float {
+    pub fn norm(&self, axis : Axis, norm : Norm) -> Matrix<T> {
+        match norm {
+            Norm::L1 => self.reduce(axis, T::zero(), |sum, _, x| sum + x.abs(), |sum, _| sum),
+            Norm::L2 => self.reduce(axis, T::zero(), |sum, _, x| sum + *x * *x, |sum, _| sum.sqrt()),
+            Norm::Inf => self.reduce(axis, T::zero(), |max, _, x| max.max(x.abs()), |max, _| max),
+        }
+    }
+}
+
+
+//LOGICAL REDUCTIONS
+impl Matrix<bool> {
+    pub fn any(&self, axis : Axis) -> Matrix<bool> { //false for an empty axis
+        self.reduce(axis, false, |any, _, x| any || *x, |any, _| any)
+    }
+
+    pub fn all(&self, axis : Axis) -> Matrix<bool> { //true for an empty axis
+        self.reduce(axis, true, |all, _, x| all && *x, |all, _| all)
+    }
+}
+
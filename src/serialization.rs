@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use crate::Matrix;
+
+
+/*
+Unchecked content of a deserialized Matrix, converted into a Matrix only if the data matches the shape
+ */
+#[derive(Deserialize)]
+pub(crate) struct RawMatrix<T> {
+    nb_lines : usize,
+    nb_columns : usize,
+    data : Vec<T>,
+}
+
+impl<T> TryFrom<RawMatrix<T>> for Matrix<T> {
+    type Error = String;
+
+    fn try_from(raw : RawMatrix<T>) -> Result<Self, Self::Error> {
+        if Some(raw.data.len()) != raw.nb_lines.checked_mul(raw.nb_columns) {
+            return Err(format!("The data contains {} elements but the shape is ({}, {})", raw.data.len(), raw.nb_lines, raw.nb_columns))
+        }
+        Ok(Matrix {
+            nb_lines : raw.nb_lines,
+            nb_columns : raw.nb_columns,
+            data : raw.data,
+        })
+    }
+}
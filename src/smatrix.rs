@@ -0,0 +1,323 @@
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use num_traits::{Float, Num, One, Zero};
+use crate::{Matrix, MatrixError};
+
+
+/*
+Matrix whose shape is known at compile time, the data is stored inline line by line
+The shapes of the operands of the operators are checked by the compiler
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SMatrix<T, const R : usize, const C : usize> {
+    data : [[T; C]; R],
+}
+
+pub type SMatrix2<T> = SMatrix<T, 2, 2>;
+pub type SMatrix3<T> = SMatrix<T, 3, 3>;
+pub type SMatrix4<T> = SMatrix<T, 4, 4>;
+
+
+//METHODS TO CREATE NEW SMATRIX
+impl<T, const R : usize, const C : usize> SMatrix<T, R, C> {
+    pub fn new(data : [[T; C]; R]) -> SMatrix<T, R, C> {
+        SMatrix {
+            data,
+        }
+    }
+
+    pub fn zeros() -> SMatrix<T, R, C> where T : Zero + Copy {
+        SMatrix {
+            data : [[T::zero(); C]; R],
+        }
+    }
+
+    pub fn ones() -> SMatrix<T, R, C> where T : One + Copy {
+        SMatrix {
+            data : [[T::one(); C]; R],
+        }
+    }
+
+    pub fn t(&self) -> SMatrix<T, C, R> where T : Copy { //return the transposed matrix
+        SMatrix {
+            data : std::array::from_fn(|j| std::array::from_fn(|i| self.data[i][j])),
+        }
+    }
+
+    pub fn map<F>(self, f : F) -> SMatrix<T, R, C> where F : Fn(T) -> T, T : Copy {
+        SMatrix {
+            data : self.data.map(|line| line.map(&f)),
+        }
+    }
+}
+
+impl<T, const N : usize> SMatrix<T, N, N> {
+    pub fn identity() -> SMatrix<T, N, N> where T : Zero + One + Copy {
+        let mut identity = SMatrix::zeros();
+        for i in 0..N {
+            identity.data[i][i] = T::one();
+        }
+        identity
+    }
+}
+
+
+//GETTERS
+impl<T, const R : usize, const C : usize> SMatrix<T, R, C> {
+    pub const fn lines(&self) -> usize {
+        R
+    }
+
+    pub const fn columns(&self) -> usize {
+        C
+    }
+
+    pub const fn size(&self) -> usize {
+        R * C
+    }
+
+    pub const fn shape(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    pub fn as_array(&self) -> &[[T; C]; R] {
+        &self.data
+    }
+
+    pub fn into_array(self) -> [[T; C]; R] {
+        self.data
+    }
+}
+
+
+//CONVERSIONS FROM AND TO MATRIX
+impl<T, const R : usize, const C : usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(matrix : SMatrix<T, R, C>) -> Self {
+        Matrix {
+            nb_lines : R,
+            nb_columns : C,
+            data : matrix.data.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<T, const R : usize, const C : usize> TryFrom<&Matrix<T>> for SMatrix<T, R, C> where T : Copy {
+    type Error = MatrixError;
+
+    fn try_from(matrix : &Matrix<T>) -> Result<Self, Self::Error> {
+        if matrix.shape() != (R, C) {
+            return Err(MatrixError::Conversion((R, C), matrix.shape()))
+        }
+        Ok(SMatrix {
+            data : std::array::from_fn(|i| std::array::from_fn(|j| matrix[i][j])),
+        })
+    }
+}
+
+impl<T, const R : usize, const C : usize> TryFrom<Matrix<T>> for SMatrix<T, R, C> where T : Copy {
+    type Error = MatrixError;
+
+    fn try_from(matrix : Matrix<T>) -> Result<Self, Self::Error> {
+        SMatrix::try_from(&matrix)
+    }
+}
+
+
+//OVERLOADING [.] operator
+impl<T, const R : usize, const C : usize> Index<usize> for SMatrix<T, R, C> {
+    type Output = [T; C];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
+    }
+}
+impl<T, const R : usize, const C : usize> IndexMut<usize> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.data[index]
+    }
+}
+
+
+//OVERLOADING +, - AND * OPERATORS
+impl<T, const R : usize, const C : usize> Add for SMatrix<T, R, C> where T : Add<Output = T> + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn add(self, rhs: Self) -> Self::Output {
+        SMatrix {
+            data : std::array::from_fn(|i| std::array::from_fn(|j| self.data[i][j] + rhs.data[i][j])),
+        }
+    }
+}
+
+impl<T, const R : usize, const C : usize> Sub for SMatrix<T, R, C> where T : Sub<Output = T> + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        SMatrix {
+            data : std::array::from_fn(|i| std::array::from_fn(|j| self.data[i][j] - rhs.data[i][j])),
+        }
+    }
+}
+impl<T, const R : usize, const C : usize> Sub for &SMatrix<T, R, C> where T : Sub<Output = T> + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        *self - *rhs
+    }
+}
+impl<T, const R : usize, const C : usize> SubAssign for SMatrix<T, R, C> where T : SubAssign + Copy {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (line, rhs_line) in self.data.iter_mut().zip(rhs.data.iter()) {
+            for (a, &b) in line.iter_mut().zip(rhs_line.iter()) {
+                *a -= b;
+            }
+        }
+    }
+}
+
+impl<T, const R : usize, const C : usize> Neg for SMatrix<T, R, C> where T : Neg<Output = T> + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+impl<T, const R : usize, const K : usize, const C : usize> Mul<SMatrix<T, K, C>> for SMatrix<T, R, K> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn mul(self, rhs: SMatrix<T, K, C>) -> Self::Output {
+        let mut res = SMatrix::zeros();
+        for i in 0..R {
+            for k in 0..K {
+                for j in 0..C {
+                    res.data[i][j] += self.data[i][k] * rhs.data[k][j];
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<T, const R : usize, const C : usize> Mul<T> for SMatrix<T, R, C> where T : Mul<Output = T> + Copy {
+    type Output = SMatrix<T, R, C>;
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|a| a * rhs)
+    }
+}
+
+
+//CLOSED-FORM DETERMINANTS AND INVERSES
+impl<T> SMatrix<T, 1, 1> where T : Num + Copy {
+    pub fn det(&self) -> T {
+        self.data[0][0]
+    }
+}
+
+impl<T> SMatrix<T, 2, 2> where T : Num + Copy {
+    pub fn det(&self) -> T {
+        let [[a, b], [c, d]] = self.data;
+        a * d - b * c
+    }
+}
+
+impl<T> SMatrix<T, 3, 3> where T : Num + Copy {
+    pub fn det(&self) -> T {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+}
+
+impl<T> SMatrix<T, 4, 4> where T : Num + Copy {
+    pub fn det(&self) -> T {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    fn minors(&self) -> ([T; 6], [T; 6]) { //2 x 2 determinants of the two upper lines and of the two lower lines
+        let a = &self.data;
+        let s = [
+            a[0][0] * a[1][1] - a[1][0] * a[0][1],
+            a[0][0] * a[1][2] - a[1][0] * a[0][2],
+            a[0][0] * a[1][3] - a[1][0] * a[0][3],
+            a[0][1] * a[1][2] - a[1][1] * a[0][2],
+            a[0][1] * a[1][3] - a[1][1] * a[0][3],
+            a[0][2] * a[1][3] - a[1][2] * a[0][3],
+        ];
+        let c = [
+            a[2][0] * a[3][1] - a[3][0] * a[2][1],
+            a[2][0] * a[3][2] - a[3][0] * a[2][2],
+            a[2][0] * a[3][3] - a[3][0] * a[2][3],
+            a[2][1] * a[3][2] - a[3][1] * a[2][2],
+            a[2][1] * a[3][3] - a[3][1] * a[2][3],
+            a[2][2] * a[3][3] - a[3][2] * a[2][3],
+        ];
+        (s, c)
+    }
+}
+
+impl<T> SMatrix<T, 1, 1> where T : Float {
+    pub fn inverse(&self) -> Result<SMatrix<T, 1, 1>, MatrixError> {
+        let det = self.det();
+        if det == T::zero() {
+            return Err(MatrixError::Singular)
+        }
+        Ok(SMatrix::new([[T::one() / det]]))
+    }
+}
+
+impl<T> SMatrix<T, 2, 2> where T : Float {
+    pub fn inverse(&self) -> Result<SMatrix<T, 2, 2>, MatrixError> {
+        let det = self.det();
+        if det == T::zero() {
+            return Err(MatrixError::Singular)
+        }
+        let [[a, b], [c, d]] = self.data;
+        Ok(SMatrix::new([[d, -b], [-c, a]]) * (T::one() / det))
+    }
+}
+
+impl<T> SMatrix<T, 3, 3> where T : Float {
+    pub fn inverse(&self) -> Result<SMatrix<T, 3, 3>, MatrixError> {
+        let det = self.det();
+        if det == T::zero() {
+            return Err(MatrixError::Singular)
+        }
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.data;
+        Ok(SMatrix::new([
+            [e * i - f * h, c * h - b * i, b * f - c * e],
+            [f * g - d * i, a * i - c * g, c * d - a * f],
+            [d * h - e * g, b * g - a * h, a * e - b * d],
+        ]) * (T::one() / det))
+    }
+}
+
+impl<T> SMatrix<T, 4, 4> where T : Float {
+    pub fn inverse(&self) -> Result<SMatrix<T, 4, 4>, MatrixError> {
+        let det = self.det();
+        if det == T::zero() {
+            return Err(MatrixError::Singular)
+        }
+        let a = &self.data;
+        let (s, c) = self.minors();
+        Ok(SMatrix::new([
+            [
+                a[1][1] * c[5] - a[1][2] * c[4] + a[1][3] * c[3],
+                -a[0][1] * c[5] + a[0][2] * c[4] - a[0][3] * c[3],
+                a[3][1] * s[5] - a[3][2] * s[4] + a[3][3] * s[3],
+                -a[2][1] * s[5] + a[2][2] * s[4] - a[2][3] * s[3],
+            ],
+            [
+                -a[1][0] * c[5] + a[1][2] * c[2] - a[1][3] * c[1],
+                a[0][0] * c[5] - a[0][2] * c[2] + a[0][3] * c[1],
+                -a[3][0] * s[5] + a[3][2] * s[2] - a[3][3] * s[1],
+                a[2][0] * s[5] - a[2][2] * s[2] + a[2][3] * s[1],
+            ],
+            [
+                a[1][0] * c[4] - a[1][1] * c[2] + a[1][3] * c[0],
+                -a[0][0] * c[4] + a[0][1] * c[2] - a[0][3] * c[0],
+                a[3][0] * s[4] - a[3][1] * s[2] + a[3][3] * s[0],
+                -a[2][0] * s[4] + a[2][1] * s[2] - a[2][3] * s[0],
+            ],
+            [
+                -a[1][0] * c[3] + a[1][1] * c[1] - a[1][2] * c[0],
+                a[0][0] * c[3] - a[0][1] * c[1] + a[0][2] * c[0],
+                -a[3][0] * s[3] + a[3][1] * s[1] - a[3][2] * s[0],
+                a[2][0] * s[3] - a[2][1] * s[1] + a[2][2] * s[0],
+            ],
+        ]) * (T::one() / det))
+    }
+}
@@ -0,0 +1,597 @@
+use std::io::{Read, Write};
+use std::ops::{Add, AddAssign, Mul, Range, Sub};
+use num_traits::Zero;
+use crate::{FormatError, Matrix, MatrixError};
+use crate::matrix_market::{self, MatrixMarketElement, MatrixMarketFormat};
+use crate::operations::check_shapes;
+
+
+/*
+Sparse matrices, only the non zero elements are stored
+- CooMatrix : list of (line, column, value) triplets, used to build the other formats
+- CsrMatrix : compressed sparse lines, fast access to the lines and fast products with dense matrices
+- CscMatrix : compressed sparse columns, fast access to the columns
+The CSR storage of a matrix is the CSC storage of its transpose, so both share the same compressed storage
+ */
+
+
+/*
+Compressed storage, the elements of the major index k are stored at the positions indptr[k]..indptr[k + 1]
+of indices (the minor indices, sorted) and values
+ */
+#[derive(Debug, Clone, PartialEq)]
+struct Compressed<T> {
+    nb_major : usize,
+    nb_minor : usize,
+    indptr : Vec<usize>,
+    indices : Vec<usize>,
+    values : Vec<T>,
+}
+
+impl<T> Compressed<T> {
+    /*
+    Build the storage from (major, minor, value) triplets in any order, the values of duplicated positions are summed
+     */
+    fn from_triplets<I>(nb_major : usize, nb_minor : usize, triplets : I) -> Compressed<T> where T : AddAssign, I : IntoIterator<Item = (usize, usize, T)> {
+        let mut triplets = triplets.into_iter().collect::<Vec<(usize, usize, T)>>();
+        triplets.sort_by_key(|(major, minor, _)| (*major, *minor));
+        let mut indptr = vec![0; nb_major + 1];
+        let mut indices = Vec::with_capacity(triplets.len());
+        let mut values : Vec<T> = Vec::with_capacity(triplets.len());
+        let mut last = None;
+        for (major, minor, value) in triplets {
+            if last == Some((major, minor)) {
+                *values.last_mut().unwrap() += value;
+            }
+            else {
+                indptr[major + 1] += 1;
+                indices.push(minor);
+                values.push(value);
+                last = Some((major, minor));
+            }
+        }
+        for k in 0..nb_major {
+            indptr[k + 1] += indptr[k];
+        }
+        Compressed {
+            nb_major,
+            nb_minor,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    fn from_dense(matrix : &Matrix<T>, tol : T, by_lines : bool) -> Compressed<T> where T : Zero + PartialOrd + Sub<Output = T> + AddAssign + Copy {
+        let triplets = (0..matrix.nb_lines)
+            .flat_map(|i| (0..matrix.nb_columns).map(move |j| (i, j, matrix[i][j])))
+            .filter(|(_, _, value)| {
+                let abs = if *value < T::zero() { T::zero() - *value } else { *value };
+                abs > tol
+            });
+        if by_lines {
+            Compressed::from_triplets(matrix.nb_lines, matrix.nb_columns, triplets)
+        }
+        else {
+            Compressed::from_triplets(matrix.nb_columns, matrix.nb_lines, triplets.map(|(i, j, value)| (j, i, value)))
+        }
+    }
+
+    fn segment(&self, major : usize) -> Range<usize> {
+        self.indptr[major]..self.indptr[major + 1]
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        (0..self.nb_major).flat_map(move |major| self.segment(major).map(move |k| (major, self.indices[k], &self.values[k])))
+    }
+
+    fn get(&self, major : usize, minor : usize) -> Option<&T> {
+        let segment = self.segment(major);
+        self.indices[segment.clone()].binary_search(&minor).ok().map(|k| &self.values[segment.start + k])
+    }
+
+    /*
+    Same matrix stored along the other index
+     */
+    fn transpose(&self) -> Compressed<T> where T : Copy {
+        let mut indptr = vec![0; self.nb_minor + 1];
+        for &minor in &self.indices {
+            indptr[minor + 1] += 1;
+        }
+        for k in 0..self.nb_minor {
+            indptr[k + 1] += indptr[k];
+        }
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.indices.len()];
+        let mut values = self.values.clone();
+        for (major, minor, value) in self.iter() {
+            indices[next[minor]] = major;
+            values[next[minor]] = *value;
+            next[minor] += 1;
+        }
+        Compressed {
+            nb_major : self.nb_minor,
+            nb_minor : self.nb_major,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    /*
+    Product computed major index by major index with a dense accumulator (Gustavson's algorithm)
+     */
+    fn mul(&self, rhs : &Compressed<T>) -> Compressed<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+        let mut accumulator = vec![T::zero(); rhs.nb_minor];
+        let mut occupied = vec![false; rhs.nb_minor];
+        let mut indptr = Vec::with_capacity(self.nb_major + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+        for major in 0..self.nb_major {
+            let begin = indices.len();
+            for k in self.segment(major) {
+                let value = self.values[k];
+                for l in rhs.segment(self.indices[k]) {
+                    let minor = rhs.indices[l];
+                    if !occupied[minor] {
+                        occupied[minor] = true;
+                        indices.push(minor);
+                    }
+                    accumulator[minor] += value * rhs.values[l];
+                }
+            }
+            indices[begin..].sort_unstable();
+            for &minor in &indices[begin..] {
+                values.push(accumulator[minor]);
+                accumulator[minor] = T::zero();
+                occupied[minor] = false;
+            }
+            indptr.push(indices.len());
+        }
+        Compressed {
+            nb_major : self.nb_major,
+            nb_minor : rhs.nb_minor,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    /*
+    Sum computed by merging the sorted segments of both operands
+     */
+    fn add(&self, rhs : &Compressed<T>) -> Compressed<T> where T : AddAssign + Copy {
+        let mut indptr = Vec::with_capacity(self.nb_major + 1);
+        let mut indices = Vec::with_capacity(self.indices.len() + rhs.indices.len());
+        let mut values = Vec::with_capacity(self.indices.len() + rhs.indices.len());
+        indptr.push(0);
+        for major in 0..self.nb_major {
+            let (mut k, mut l) = (self.indptr[major], rhs.indptr[major]);
+            let (k_end, l_end) = (self.indptr[major + 1], rhs.indptr[major + 1]);
+            while k < k_end || l < l_end {
+                if l == l_end || (k < k_end && self.indices[k] < rhs.indices[l]) {
+                    indices.push(self.indices[k]);
+                    values.push(self.values[k]);
+                    k += 1;
+                }
+                else if k == k_end || rhs.indices[l] < self.indices[k] {
+                    indices.push(rhs.indices[l]);
+                    values.push(rhs.values[l]);
+                    l += 1;
+                }
+                else {
+                    let mut value = self.values[k];
+                    value += rhs.values[l];
+                    indices.push(self.indices[k]);
+                    values.push(value);
+                    k += 1;
+                    l += 1;
+                }
+            }
+            indptr.push(indices.len());
+        }
+        Compressed {
+            nb_major : self.nb_major,
+            nb_minor : self.nb_minor,
+            indptr,
+            indices,
+            values,
+        }
+    }
+}
+
+
+/*
+Products between a sparse matrix given by its (line, column, value) elements and a dense matrix
+ */
+fn sparse_dense_mul<'a, T, I>(shape : (usize, usize), elements : I, rhs : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : 'a + Mul<Output = T> + AddAssign + Zero + Copy, I : Iterator<Item = (usize, usize, &'a T)> {
+    if shape.1 != rhs.nb_lines {
+        return Err(MatrixError::Multiplication(shape, rhs.shape()))
+    }
+    let mut res = Matrix::zeros(shape.0, rhs.nb_columns);
+    for (i, k, value) in elements {
+        for (res, rhs) in res[i].iter_mut().zip(&rhs[k]) {
+            *res += *value * *rhs;
+        }
+    }
+    Ok(res)
+}
+
+fn dense_sparse_mul<'a, T, I>(lhs : &Matrix<T>, shape : (usize, usize), elements : I) -> Result<Matrix<T>, MatrixError> where T : 'a + Mul<Output = T> + AddAssign + Zero + Copy, I : Iterator<Item = (usize, usize, &'a T)> {
+    if lhs.nb_columns != shape.0 {
+        return Err(MatrixError::Multiplication(lhs.shape(), shape))
+    }
+    let mut res = Matrix::zeros(lhs.nb_lines, shape.1);
+    for (k, j, value) in elements {
+        for i in 0..lhs.nb_lines {
+            res[i][j] += lhs[i][k] * *value;
+        }
+    }
+    Ok(res)
+}
+
+
+
+
+//COORDINATE FORMAT
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    nb_lines : usize,
+    nb_columns : usize,
+    entries : Vec<(usize, usize, T)>,
+}
+
+impl<T> CooMatrix<T> {
+    pub fn new(nb_lines : usize, nb_columns : usize) -> CooMatrix<T> {
+        CooMatrix::with_capacity(nb_lines, nb_columns, 0)
+    }
+
+    pub fn with_capacity(nb_lines : usize, nb_columns : usize, capacity : usize) -> CooMatrix<T> {
+        CooMatrix {
+            nb_lines,
+            nb_columns,
+            entries : Vec::with_capacity(capacity),
+        }
+    }
+
+    /*
+    Add an element, the values pushed several times at the same position are summed by the conversions
+    Panics if the position is outside of the matrix
+     */
+    pub fn push(&mut self, line : usize, column : usize, value : T) {
+        assert!(line < self.nb_lines && column < self.nb_columns, "The position {:?} is outside of a matrix of shape {:?}", (line, column), self.shape());
+        self.entries.push((line, column, value));
+    }
+
+    pub fn lines(&self) -> usize {
+        self.nb_lines
+    }
+
+    pub fn columns(&self) -> usize {
+        self.nb_columns
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nb_lines, self.nb_columns)
+    }
+
+    pub fn nnz(&self) -> usize { //number of stored entries, duplicates included
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.entries.iter().map(|(i, j, value)| (*i, *j, value))
+    }
+
+    pub fn t(&self) -> CooMatrix<T> where T : Copy {
+        CooMatrix {
+            nb_lines : self.nb_columns,
+            nb_columns : self.nb_lines,
+            entries : self.entries.iter().map(|(i, j, value)| (*j, *i, *value)).collect(),
+        }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> where T : AddAssign + Copy {
+        CsrMatrix {
+            storage : Compressed::from_triplets(self.nb_lines, self.nb_columns, self.entries.iter().copied()),
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> where T : AddAssign + Copy {
+        CscMatrix {
+            storage : Compressed::from_triplets(self.nb_columns, self.nb_lines, self.entries.iter().map(|(i, j, value)| (*j, *i, *value))),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> where T : AddAssign + Zero + Copy {
+        let mut matrix = Matrix::zeros(self.nb_lines, self.nb_columns);
+        for (i, j, value) in &self.entries {
+            matrix[*i][*j] += *value;
+        }
+        matrix
+    }
+
+    /*
+    Keep the elements whose absolute value is greater than tol
+     */
+    pub fn from_dense(matrix : &Matrix<T>, tol : T) -> CooMatrix<T> where T : Zero + PartialOrd + Sub<Output = T> + AddAssign + Copy {
+        CsrMatrix::from_dense(matrix, tol).to_coo()
+    }
+
+    /*
+    Read a coordinate or an array Matrix Market file, the symmetric matrices are expanded
+     */
+    pub fn from_matrix_market<R>(reader : R) -> Result<CooMatrix<T>, FormatError> where T : MatrixMarketElement, R : Read {
+        let entries = matrix_market::read_entries(reader)?;
+        Ok(CooMatrix {
+            nb_lines : entries.nb_lines,
+            nb_columns : entries.nb_columns,
+            entries : entries.triplets,
+        })
+    }
+
+    pub fn to_matrix_market<W>(&self, writer : W) -> Result<(), FormatError> where T : MatrixMarketElement, W : Write {
+        matrix_market::write_entries(writer, MatrixMarketFormat::Coordinate, false, self.shape(), self.nnz(), self.entries.iter().copied())
+    }
+
+    /*
+    Write a symmetric matrix as a symmetric coordinate file containing only its lower triangle, the duplicates are summed first
+    FormatError::InvalidData is returned if the matrix is not symmetric
+     */
+    pub fn to_matrix_market_symmetric<W>(&self, writer : W) -> Result<(), FormatError> where T : MatrixMarketElement + AddAssign, W : Write {
+        let csr = self.to_csr();
+        if self.nb_lines != self.nb_columns || csr != self.t().to_csr() {
+            return Err(FormatError::InvalidData("Only a symmetric matrix can be written as a symmetric file".to_string()))
+        }
+        let lower = csr.to_coo().entries.into_iter().filter(|(i, j, _)| i >= j).collect::<Vec<(usize, usize, T)>>();
+        matrix_market::write_entries(writer, MatrixMarketFormat::Coordinate, true, self.shape(), lower.len(), lower)
+    }
+}
+
+
+
+//COMPRESSED SPARSE LINES FORMAT
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    storage : Compressed<T>,
+}
+
+impl<T> CsrMatrix<T> {
+    pub fn lines(&self) -> usize {
+        self.storage.nb_major
+    }
+
+    pub fn columns(&self) -> usize {
+        self.storage.nb_minor
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.lines(), self.columns())
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.storage.values.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] { //the elements of the line i are at the positions indptr[i]..indptr[i + 1]
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] { //column of each element
+        &self.storage.indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.storage.values
+    }
+
+    pub fn get(&self, line : usize, column : usize) -> Option<&T> { //None if the element is not stored
+        self.storage.get(line, column)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> { //elements line by line
+        self.storage.iter()
+    }
+
+    pub fn t(self) -> CscMatrix<T> { //no copy, the CSR storage of a matrix is the CSC storage of its transpose
+        CscMatrix {
+            storage : self.storage,
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> where T : Copy {
+        CscMatrix {
+            storage : self.storage.transpose(),
+        }
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> where T : Copy {
+        CooMatrix {
+            nb_lines : self.lines(),
+            nb_columns : self.columns(),
+            entries : self.iter().map(|(i, j, value)| (i, j, *value)).collect(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> where T : Zero + Copy {
+        let mut matrix = Matrix::zeros(self.lines(), self.columns());
+        for (i, j, value) in self.iter() {
+            matrix[i][j] = *value;
+        }
+        matrix
+    }
+
+    pub fn from_dense(matrix : &Matrix<T>, tol : T) -> CsrMatrix<T> where T : Zero + PartialOrd + Sub<Output = T> + AddAssign + Copy {
+        CsrMatrix {
+            storage : Compressed::from_dense(matrix, tol, true),
+        }
+    }
+
+    pub fn try_add(&self, rhs : &CsrMatrix<T>) -> Result<CsrMatrix<T>, MatrixError> where T : AddAssign + Copy {
+        check_shapes(self.shape(), rhs.shape(), MatrixError::Adding)?;
+        Ok(CsrMatrix {
+            storage : self.storage.add(&rhs.storage),
+        })
+    }
+
+    pub fn try_mul(&self, rhs : &CsrMatrix<T>) -> Result<CsrMatrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+        if self.columns() != rhs.lines() {
+            return Err(MatrixError::Multiplication(self.shape(), rhs.shape()))
+        }
+        Ok(CsrMatrix {
+            storage : self.storage.mul(&rhs.storage),
+        })
+    }
+
+    pub fn try_mul_dense(&self, rhs : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy { //self * rhs
+        sparse_dense_mul(self.shape(), self.iter(), rhs)
+    }
+
+    pub fn try_dense_mul(&self, lhs : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy { //lhs * self
+        dense_sparse_mul(lhs, self.shape(), self.iter())
+    }
+}
+
+
+
+//COMPRESSED SPARSE COLUMNS FORMAT
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<T> {
+    storage : Compressed<T>,
+}
+
+impl<T> CscMatrix<T> {
+    pub fn lines(&self) -> usize {
+        self.storage.nb_minor
+    }
+
+    pub fn columns(&self) -> usize {
+        self.storage.nb_major
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.lines(), self.columns())
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.storage.values.len()
+    }
+
+    pub fn indptr(&self) -> &[usize] { //the elements of the column j are at the positions indptr[j]..indptr[j + 1]
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] { //line of each element
+        &self.storage.indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.storage.values
+    }
+
+    pub fn get(&self, line : usize, column : usize) -> Option<&T> { //None if the element is not stored
+        self.storage.get(column, line)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> { //elements column by column
+        self.storage.iter().map(|(j, i, value)| (i, j, value))
+    }
+
+    pub fn t(self) -> CsrMatrix<T> { //no copy, the CSC storage of a matrix is the CSR storage of its transpose
+        CsrMatrix {
+            storage : self.storage,
+        }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> where T : Copy {
+        CsrMatrix {
+            storage : self.storage.transpose(),
+        }
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> where T : Copy {
+        CooMatrix {
+            nb_lines : self.lines(),
+            nb_columns : self.columns(),
+            entries : self.iter().map(|(i, j, value)| (i, j, *value)).collect(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> where T : Zero + Copy {
+        let mut matrix = Matrix::zeros(self.lines(), self.columns());
+        for (i, j, value) in self.iter() {
+            matrix[i][j] = *value;
+        }
+        matrix
+    }
+
+    pub fn from_dense(matrix : &Matrix<T>, tol : T) -> CscMatrix<T> where T : Zero + PartialOrd + Sub<Output = T> + AddAssign + Copy {
+        CscMatrix {
+            storage : Compressed::from_dense(matrix, tol, false),
+        }
+    }
+
+    pub fn try_add(&self, rhs : &CscMatrix<T>) -> Result<CscMatrix<T>, MatrixError> where T : AddAssign + Copy {
+        check_shapes(self.shape(), rhs.shape(), MatrixError::Adding)?;
+        Ok(CscMatrix {
+            storage : self.storage.add(&rhs.storage),
+        })
+    }
+
+    pub fn try_mul(&self, rhs : &CscMatrix<T>) -> Result<CscMatrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+        if self.columns() != rhs.lines() {
+            return Err(MatrixError::Multiplication(self.shape(), rhs.shape()))
+        }
+        Ok(CscMatrix { //the storages are the CSR storages of the transposes and (A * B)^T = B^T * A^T
+            storage : rhs.storage.mul(&self.storage),
+        })
+    }
+
+    pub fn try_mul_dense(&self, rhs : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy { //self * rhs
+        sparse_dense_mul(self.shape(), self.iter(), rhs)
+    }
+
+    pub fn try_dense_mul(&self, lhs : &Matrix<T>) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + Copy { //lhs * self
+        dense_sparse_mul(lhs, self.shape(), self.iter())
+    }
+}
+
+
+
+//OVERLOADING + AND * OPERATORS FOR SPARSE MATRICES
+macro_rules! sparse_operators {
+    ($($t:ident),*) => {
+        $(
+            impl<'a, T> Add<&'a $t<T>> for &'a $t<T> where T : AddAssign + Copy {
+                type Output = $t<T>;
+
+                fn add(self, rhs : &'a $t<T>) -> Self::Output {
+                    self.try_add(rhs).unwrap_or_else(|error| panic!("{}", error))
+                }
+            }
+            impl<'a, T> Mul<&'a $t<T>> for &'a $t<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+                type Output = $t<T>;
+
+                fn mul(self, rhs : &'a $t<T>) -> Self::Output {
+                    self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+                }
+            }
+            impl<'a, T> Mul<&'a Matrix<T>> for &'a $t<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+                type Output = Matrix<T>;
+
+                fn mul(self, rhs : &'a Matrix<T>) -> Self::Output {
+                    self.try_mul_dense(rhs).unwrap_or_else(|error| panic!("{}", error))
+                }
+            }
+            impl<'a, T> Mul<&'a $t<T>> for &'a Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+                type Output = Matrix<T>;
+
+                fn mul(self, rhs : &'a $t<T>) -> Self::Output {
+                    rhs.try_dense_mul(self).unwrap_or_else(|error| panic!("{}", error))
+                }
+            }
+        )*
+    };
+}
+sparse_operators!(CsrMatrix, CscMatrix);
@@ -0,0 +1,289 @@
+use num_traits::Float;
+use crate::{Matrix, MatrixError};
+
+
+/*
+Size of the orthogonal factors of the singular value decomposition of a m x n matrix, with k = min(m, n)
+- Thin : U is m x k and V^T is k x n
+- Full : U is m x m and V^T is n x n
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SVDMode {
+    Thin,
+    Full,
+}
+
+/*
+Singular value decomposition : A = U * Sigma * V^T
+- u : Option<Matrix<T>>             the left singular vectors in columns, None if not computed
+- singular_values : Vec<T>          the min(m, n) singular values sorted in decreasing order
+- vt : Option<Matrix<T>>            the right singular vectors in lines, None if not computed
+ */
+#[derive(Debug, Clone)]
+pub struct SVD<T> {
+    u : Option<Matrix<T>>,
+    singular_values : Vec<T>,
+    vt : Option<Matrix<T>>,
+    shape : (usize, usize),
+}
+
+const MAX_SWEEPS : usize = 64; //the one-sided Jacobi method converges quadratically, so a few sweeps are usually enough
+
+
+//METHODS TO CREATE THE DECOMPOSITION
+impl<T> Matrix<T> where T : Float {
+    pub fn try_svd(&self, mode : SVDMode) -> Result<SVD<T>, MatrixError> {
+        self.try_svd_with(mode, true, true)
+    }
+
+    pub fn svd(&self, mode : SVDMode) -> SVD<T> {
+        self.try_svd(mode).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_singular_values(&self) -> Result<Vec<T>, MatrixError> {
+        Ok(self.try_svd_with(SVDMode::Thin, false, false)?.singular_values)
+    }
+
+    pub fn singular_values(&self) -> Vec<T> {
+        self.try_singular_values().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /*
+    Compute the singular value decomposition using the one-sided Jacobi method
+    - mode : SVDMode            size of the computed factors
+    - compute_u : bool          if false U is not computed
+    - compute_v : bool          if false V^T is not computed
+    MatrixError::NoConvergence is returned if the columns are still not orthogonal after MAX_SWEEPS sweeps
+     */
+    pub fn try_svd_with(&self, mode : SVDMode, compute_u : bool, compute_v : bool) -> Result<SVD<T>, MatrixError> {
+        if self.nb_lines >= self.nb_columns {
+            let (u, singular_values, v) = jacobi(self.clone(), mode, compute_u, compute_v)?;
+            Ok(SVD {
+                u,
+                singular_values,
+                vt : v.map(|v| v.t()),
+                shape : self.shape(),
+            })
+        }
+        else { //A^T = U' * Sigma * V'^T so A = V' * Sigma * U'^T
+            let (u, singular_values, v) = jacobi(self.t(), mode, compute_v, compute_u)?;
+            Ok(SVD {
+                u : v,
+                singular_values,
+                vt : u.map(|u| u.t()),
+                shape : self.shape(),
+            })
+        }
+    }
+
+    pub fn svd_with(&self, mode : SVDMode, compute_u : bool, compute_v : bool) -> SVD<T> {
+        self.try_svd_with(mode, compute_u, compute_v).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+/*
+One-sided Jacobi on a m x n matrix with m >= n, return U, the singular values and V (not transposed)
+The columns of a are orthogonalized by plane rotations, V accumulates these rotations
+ */
+#[allow(clippy::type_complexity)]
+fn jacobi<T>(mut a : Matrix<T>, mode : SVDMode, compute_u : bool, compute_v : bool) -> Result<(Option<Matrix<T>>, Vec<T>, Option<Matrix<T>>), MatrixError> where T : Float {
+    let (m, n) = a.shape();
+    let mut v = Matrix::identity(n);
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+                for i in 0..m {
+                    alpha = alpha + a[i][p] * a[i][p];
+                    beta = beta + a[i][q] * a[i][q];
+                    gamma = gamma + a[i][p] * a[i][q];
+                }
+                if gamma == T::zero() || gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (gamma + gamma);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                for i in 0..m {
+                    let (x, y) = (a[i][p], a[i][q]);
+                    a[i][p] = c * x - s * y;
+                    a[i][q] = s * x + c * y;
+                }
+                if compute_v {
+                    for i in 0..n {
+                        let (x, y) = (v[i][p], v[i][q]);
+                        v[i][p] = c * x - s * y;
+                        v[i][q] = s * x + c * y;
+                    }
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break
+        }
+    }
+    if !converged {
+        return Err(MatrixError::NoConvergence)
+    }
+
+    let norms : Vec<T> = (0..n).map(|j| (0..m).fold(T::zero(), |sum, i| sum + a[i][j] * a[i][j]).sqrt()).collect();
+    let mut order : Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].partial_cmp(&norms[i]).unwrap_or(std::cmp::Ordering::Equal));
+    let singular_values : Vec<T> = order.iter().map(|&j| norms[j]).collect();
+
+    let u = if compute_u {
+        let nb_columns = match mode {
+            SVDMode::Thin => n,
+            SVDMode::Full => m,
+        };
+        let mut u = Matrix::zeros(m, nb_columns);
+        let mut filled = vec![false; nb_columns];
+        let threshold = singular_values.first().map_or(T::zero(), |&largest| largest * T::epsilon());
+        for (k, &j) in order.iter().enumerate() {
+            if norms[j] > threshold {
+                for i in 0..m {
+                    u[i][k] = a[i][j] / norms[j];
+                }
+                filled[k] = true;
+            }
+        }
+        complete_basis(&mut u, filled);
+        Some(u)
+    }
+    else {
+        None
+    };
+    let v = if compute_v {
+        Some(v.chose_columns_by_index(&order))
+    }
+    else {
+        None
+    };
+    Ok((u, singular_values, v))
+}
+
+/*
+Fill the columns of q which are not yet filled so that q has orthonormal columns
+The candidates are the vectors of the canonical basis, orthogonalized twice against the filled columns
+ */
+fn complete_basis<T>(q : &mut Matrix<T>, mut filled : Vec<bool>) where T : Float {
+    let (m, k) = q.shape();
+    let mut candidate = 0;
+    for j in 0..k {
+        if filled[j] {
+            continue
+        }
+        while candidate < m {
+            let mut x = vec![T::zero(); m];
+            x[candidate] = T::one();
+            candidate += 1;
+            for _ in 0..2 {
+                for c in (0..k).filter(|&c| filled[c]) {
+                    let dot = (0..m).fold(T::zero(), |sum, i| sum + q[i][c] * x[i]);
+                    for (i, x_i) in x.iter_mut().enumerate() {
+                        *x_i = *x_i - dot * q[i][c];
+                    }
+                }
+            }
+            let norm = x.iter().fold(T::zero(), |sum, &x_i| sum + x_i * x_i).sqrt();
+            if norm > T::from(0.5).unwrap_or_else(T::epsilon) {
+                for (i, x_i) in x.into_iter().enumerate() {
+                    q[i][j] = x_i / norm;
+                }
+                filled[j] = true;
+                break
+            }
+        }
+    }
+}
+
+
+//GETTERS
+impl<T> SVD<T> where T : Float {
+    pub fn u(&self) -> Option<&Matrix<T>> {
+        self.u.as_ref()
+    }
+
+    pub fn vt(&self) -> Option<&Matrix<T>> {
+        self.vt.as_ref()
+    }
+
+    pub fn singular_values(&self) -> &[T] {
+        &self.singular_values
+    }
+
+    pub fn sigma(&self) -> Matrix<T> { //return Sigma with the shape matching the computed U and V^T
+        let nb_lines = self.u.as_ref().map_or(self.singular_values.len(), |u| u.nb_columns);
+        let nb_columns = self.vt.as_ref().map_or(self.singular_values.len(), |vt| vt.nb_lines);
+        let mut sigma = Matrix::zeros(nb_lines, nb_columns);
+        for (i, &value) in self.singular_values.iter().enumerate() {
+            sigma[i][i] = value;
+        }
+        sigma
+    }
+}
+
+
+//FUNCTIONALITIES
+impl<T> SVD<T> where T : Float {
+    pub fn rank(&self, tolerance : T) -> usize {
+        self.singular_values.iter().filter(|&&value| value > tolerance).count()
+    }
+
+    /*
+    Return the Moore-Penrose pseudo-inverse, the singular values lower or equal to rcond * max(singular values) are considered null
+    Panic if U or V^T has not been computed
+     */
+    pub fn pinv(&self, rcond : T) -> Matrix<T> {
+        let u = self.u.as_ref().expect("U must be computed to get the pseudo-inverse");
+        let vt = self.vt.as_ref().expect("V^T must be computed to get the pseudo-inverse");
+        let (m, n) = self.shape;
+        let cutoff = rcond * self.singular_values.first().copied().unwrap_or_else(T::zero);
+        let mut pinv = Matrix::zeros(n, m);
+        for (k, &value) in self.singular_values.iter().enumerate() {
+            if value <= cutoff || value == T::zero() {
+                continue
+            }
+            for i in 0..n {
+                let factor = vt[k][i] / value;
+                for j in 0..m {
+                    pinv[i][j] = pinv[i][j] + factor * u[j][k];
+                }
+            }
+        }
+        pinv
+    }
+}
+
+
+//NORMS AND CONDITIONING
+impl<T> Matrix<T> where T : Float {
+    pub fn pinv(&self, rcond : T) -> Matrix<T> {
+        self.svd(SVDMode::Thin).pinv(rcond)
+    }
+
+    pub fn rank(&self, tolerance : T) -> usize {
+        self.singular_values().into_iter().filter(|&value| value > tolerance).count()
+    }
+
+    pub fn condition_number(&self) -> T { //ratio of the largest and smallest singular values, infinite for a singular matrix, NaN for an empty matrix
+        let singular_values = self.singular_values();
+        match (singular_values.first(), singular_values.last()) {
+            (Some(&largest), Some(&smallest)) => largest / smallest,
+            _ => T::nan(),
+        }
+    }
+
+    pub fn nuclear_norm(&self) -> T {
+        self.singular_values().into_iter().fold(T::zero(), |sum, value| sum + value)
+    }
+
+    pub fn spectral_norm(&self) -> T {
+        self.singular_values().first().copied().unwrap_or_else(T::zero)
+    }
+}
@@ -1,6 +1,6 @@
-#[cfg(test)]
-
-use crate::Matrix;
+use crate::{gemm, mul_into, nn, Axis, CsvOptions, FormatError, Matrix, MatrixError, MatrixMarketFormat, MissingValues, Norm, SMatrix, SMatrix2, SMatrix3, SMatrix4, SVDMode};
+use crate::autograd::{Function, Tape};
+use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
 
 #[test]
 fn multiplication() {
@@ -118,3 +118,1116 @@ fn addition_lines() {
 
 
 
+
+
+#[test]
+fn fallible_operations() {
+    let matrix1 = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![2,   5,
+                    3,  -7,
+                    5,   0]
+    };
+
+    let matrix2 = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![1, 0,
+                    0, 1]
+    };
+
+    assert_eq!(matrix1.try_add(&matrix2), Err(MatrixError::Adding((3, 2), (2, 2))), "Testing try_add with different shapes");
+    assert_eq!(matrix2.try_mul(&matrix1), Err(MatrixError::Multiplication((2, 2), (3, 2))), "Testing try_mul with incompatible shapes");
+    assert_eq!(matrix1.try_hadamard(&matrix2), Err(MatrixError::HadamardProduct((3, 2), (2, 2))), "Testing try_hadamard with different shapes");
+    assert_eq!(matrix1.try_mul(&matrix2), Ok(matrix1.clone()), "Testing try_mul with the identity");
+    assert_eq!(matrix1.clone().try_split_lines(4).err(), Some(MatrixError::Splitting((3, 2), 4)), "Testing try_split_lines with too many parts");
+    assert_eq!(matrix1.clone().try_split_lines(0).err(), Some(MatrixError::Splitting((3, 2), 0)), "Testing try_split_lines with zero parts");
+    assert_eq!(matrix1.try_concatenate_lines(Matrix::<i32>::zeros(1, 3)), Err(MatrixError::Concatenation((3, 2), (1, 3))), "Testing try_concatenate_lines with different number of columns");
+}
+
+#[test]
+#[should_panic(expected = "Can't add two matrices with different shape !!")]
+fn addition_panics_on_shape_mismatch() {
+    let _ = Matrix::<i32>::zeros(3, 2) + Matrix::<i32>::zeros(2, 2);
+}
+
+fn assert_close(left : &Matrix<f64>, right : &Matrix<f64>, message : &str) {
+    assert_eq!(left.shape(), right.shape(), "{}", message);
+    for (a, b) in left.into_iter().zip(right) {
+        assert!((a - b).abs() < 1e-9, "{} : {:?} != {:?}", message, left, right);
+    }
+}
+
+#[test]
+fn lu_decomposition() {
+    let matrix = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![2.,  1.,  1.,
+                    4., -6.,  0.,
+                   -2.,  7.,  2.]
+    };
+
+    let lu = matrix.lu().unwrap();
+    assert_close(&(lu.p() * &matrix), &(lu.l() * lu.u()), "Testing P * A = L * U");
+    assert!((lu.det() + 16.).abs() < 1e-9, "Testing the determinant");
+    assert_close(&(&matrix * &matrix.inverse().unwrap()), &Matrix::identity(3), "Testing the inverse");
+
+    let b = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![5., 1.,
+                   -2., 0.,
+                    9., 0.]
+    };
+    assert_close(&(&matrix * &lu.solve(&b).unwrap()), &b, "Testing solve with two right-hand sides");
+
+    let singular = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![1., 2.,
+                    2., 4.]
+    };
+    assert!(singular.lu().unwrap().is_singular(), "Testing singularity detection");
+    assert_eq!(singular.inverse(), Err(MatrixError::Singular), "Testing the inverse of a singular matrix");
+    assert_eq!(Matrix::<f64>::zeros(2, 3).det(), Err(MatrixError::NotSquare((2, 3))), "Testing the determinant of a non square matrix");
+}
+
+#[test]
+fn qr_decomposition() {
+    let matrix = Matrix {
+        nb_lines : 4,
+        nb_columns : 3,
+        data : vec![1., 2., 3.,
+                    4., 5., 6.,
+                    7., 8., 10.,
+                    1., 0., 1.]
+    };
+
+    let qr = matrix.qr();
+    assert_close(&(qr.thin_q() * qr.thin_r()), &matrix, "Testing A = Q * R with the thin factorization");
+    assert_close(&(qr.full_q() * qr.full_r()), &matrix, "Testing A = Q * R with the full factorization");
+    assert_close(&(qr.full_q().t() * qr.full_q()), &Matrix::identity(4), "Testing the orthogonality of Q");
+
+    let pivoted = matrix.qr_pivoted();
+    assert_close(&(pivoted.thin_q() * pivoted.thin_r()), &(&matrix * &pivoted.p()), "Testing A * P = Q * R");
+
+    let x = Matrix {
+        nb_lines : 3,
+        nb_columns : 1,
+        data : vec![1., -1., 2.]
+    };
+    let least_squares = matrix.lstsq(&matrix * &x).unwrap();
+    assert_close(&least_squares.solution, &x, "Testing lstsq on a consistent system");
+    assert_eq!(least_squares.rank, 3, "Testing the rank of a full rank matrix");
+    assert!(least_squares.residuals[0][0] < 1e-9, "Testing the residuals of a consistent system");
+
+    let deficient : Matrix<f64> = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![1., 2.,
+                    2., 4.,
+                    3., 6.]
+    };
+    let b = Matrix {
+        nb_lines : 3,
+        nb_columns : 1,
+        data : vec![1., 0., 0.]
+    };
+    let least_squares = deficient.lstsq(&b).unwrap();
+    assert_eq!(least_squares.rank, 1, "Testing the rank of a rank-deficient matrix");
+    assert!((least_squares.residuals[0][0] - 13. / 14.).abs() < 1e-9, "Testing the residuals of a rank-deficient system");
+
+    let null_column : Matrix<f64> = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![0., 1.,
+                    0., 2.,
+                    0., 3.]
+    };
+    let b = Matrix {
+        nb_lines : 3,
+        nb_columns : 1,
+        data : vec![1., 2., 3.]
+    };
+    assert_eq!(null_column.qr().lstsq(&b).err(), Some(MatrixError::Singular), "Testing lstsq without pivoting on a rank-deficient matrix");
+    let least_squares = null_column.lstsq(&b).unwrap();
+    assert_eq!(least_squares.rank, 1, "Testing the rank with a null column");
+    assert_close(&least_squares.solution, &Matrix { nb_lines : 2, nb_columns : 1, data : vec![0., 1.] }, "Testing lstsq with pivoting on a rank-deficient matrix");
+}
+
+#[test]
+fn symmetric_factorizations() {
+    let matrix = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![ 4.,  12., -16.,
+                    12.,  37., -43.,
+                   -16., -43.,  98.]
+    };
+
+    let cholesky = matrix.cholesky().unwrap();
+    let l = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![ 2., 0., 0.,
+                     6., 1., 0.,
+                    -8., 5., 3.]
+    };
+    assert_close(cholesky.l(), &l, "Testing the Cholesky factor");
+    assert!((cholesky.log_det() - 36f64.ln()).abs() < 1e-9, "Testing the Cholesky log determinant");
+    assert_close(&(&matrix * &cholesky.inverse()), &Matrix::identity(3), "Testing the Cholesky inverse");
+
+    let indefinite = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![1., 2.,
+                    2., 1.]
+    };
+    assert_eq!(indefinite.cholesky().err(), Some(MatrixError::NotPositiveDefinite), "Testing Cholesky on an indefinite matrix");
+
+    let ldlt = indefinite.ldlt().unwrap();
+    let (sign, log_det) = ldlt.log_det();
+    assert_eq!(sign, -1., "Testing the sign of the LDLt determinant");
+    assert!((log_det - 3f64.ln()).abs() < 1e-9, "Testing the LDLt log determinant");
+    let b = Matrix {
+        nb_lines : 2,
+        nb_columns : 1,
+        data : vec![3., 0.]
+    };
+    assert_close(&(&indefinite * &ldlt.solve(&b).unwrap()), &b, "Testing the LDLt solve");
+
+    let swap = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![0., 1.,
+                    1., 0.]
+    };
+    let ldlt = swap.ldlt().unwrap();
+    assert_close(&(ldlt.p().t() * &swap * ldlt.p()), &(ldlt.l() * ldlt.d() * ldlt.l().t()), "Testing P^T * A * P = L * D * L^T with a null leading minor");
+    assert!((ldlt.det() + 1.).abs() < 1e-12, "Testing the determinant with a 2 x 2 block");
+    assert_close(&ldlt.inverse(), &swap, "Testing the inverse with a 2 x 2 block");
+
+    let zero_pivot = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![0., 2., 1.,
+                    2., 0., 3.,
+                    1., 3., 4.]
+    };
+    let ldlt = zero_pivot.ldlt().unwrap();
+    assert_close(&(ldlt.p().t() * &zero_pivot * ldlt.p()), &(ldlt.l() * ldlt.d() * ldlt.l().t()), "Testing the pivoted LDLt factors with a zero pivot");
+    assert_close(&(&zero_pivot * &ldlt.inverse()), &Matrix::identity(3), "Testing the pivoted LDLt inverse");
+    let (sign, log_det) = ldlt.log_det();
+    assert!((sign * log_det.exp() - zero_pivot.det().unwrap()).abs() < 1e-9, "Testing the pivoted LDLt log determinant");
+    let b = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![1., 0.,
+                    2., -1.,
+                    3., 5.]
+    };
+    assert_close(&(&zero_pivot * &ldlt.solve(&b).unwrap()), &b, "Testing the pivoted LDLt solve");
+    assert_eq!(Matrix::<f64>::zeros(2, 2).ldlt().err(), Some(MatrixError::Singular), "Testing LDLt on a null matrix");
+}
+
+#[test]
+fn singular_value_decomposition() {
+    let matrix : Matrix<f64> = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![3., 2.,  2.,
+                    2., 3., -2.]
+    };
+
+    let svd = matrix.svd(SVDMode::Thin);
+    assert!((svd.singular_values()[0] - 5.).abs() < 1e-9 && (svd.singular_values()[1] - 3.).abs() < 1e-9, "Testing the singular values");
+    assert_close(&(svd.u().unwrap() * &svd.sigma() * svd.vt().unwrap()), &matrix, "Testing A = U * Sigma * V^T with the thin decomposition");
+
+    let full = matrix.svd(SVDMode::Full);
+    assert_eq!(full.vt().unwrap().shape(), (3, 3), "Testing the shape of the full V^T");
+    assert_close(&(full.u().unwrap() * &full.sigma() * full.vt().unwrap()), &matrix, "Testing A = U * Sigma * V^T with the full decomposition");
+    assert_close(&(full.vt().unwrap() * &full.vt().unwrap().t()), &Matrix::identity(3), "Testing the orthogonality of the full V^T");
+
+    let partial = matrix.svd_with(SVDMode::Thin, false, true);
+    assert!(partial.u().is_none() && partial.vt().is_some(), "Testing the decomposition without U");
+    assert!(matrix.try_svd(SVDMode::Thin).is_ok(), "Testing the convergence of the fallible decomposition");
+    assert_eq!(matrix.try_singular_values().unwrap().len(), 2, "Testing the fallible singular values");
+
+    let pinv = matrix.pinv(1e-12);
+    assert_close(&(&(&matrix * &pinv) * &matrix), &matrix, "Testing A * A^+ * A = A");
+    assert_eq!(matrix.rank(1e-9), 2, "Testing the rank");
+    assert!((matrix.spectral_norm() - 5.).abs() < 1e-9, "Testing the spectral norm");
+    assert!((matrix.nuclear_norm() - 8.).abs() < 1e-9, "Testing the nuclear norm");
+    assert!((matrix.condition_number() - 5. / 3.).abs() < 1e-9, "Testing the condition number");
+    assert!(Matrix::<f64>::zeros(0, 0).condition_number().is_nan(), "Testing the condition number of an empty matrix");
+}
+
+#[test]
+fn eigen_decompositions() {
+    let symmetric : Matrix<f64> = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![ 2., -1.,  0.,
+                    -1.,  2., -1.,
+                     0., -1.,  2.]
+    };
+
+    let eigen = symmetric.symmetric_eigen().unwrap();
+    let expected = [2. + 2f64.sqrt(), 2., 2. - 2f64.sqrt()];
+    for (value, expected) in eigen.eigenvalues().iter().zip(expected) {
+        assert!((value - expected).abs() < 1e-9, "Testing the symmetric eigenvalues");
+    }
+    let v = eigen.eigenvectors();
+    let mut d = Matrix::zeros(3, 3);
+    for i in 0..3 {
+        d[i][i] = eigen.eigenvalues()[i];
+    }
+    assert_close(&(v.t() * v), &Matrix::identity(3), "Testing the orthonormality of the eigenvectors");
+    assert_close(&(v * &d * v.t()), &symmetric, "Testing A = V * D * V^T");
+
+    let rotation : Matrix<f64> = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![0., -1., 0.,
+                    1.,  0., 0.,
+                    0.,  0., 0.5]
+    };
+    let eigenvalues = rotation.eigenvalues().unwrap();
+    assert_eq!(eigenvalues.len(), 3, "Testing the number of general eigenvalues");
+    assert!((eigenvalues[0].re).abs() < 1e-9 && (eigenvalues[0].im.abs() - 1.).abs() < 1e-9, "Testing a complex eigenvalue");
+    assert!((eigenvalues[0].im + eigenvalues[1].im).abs() < 1e-9, "Testing the conjugate pair");
+    assert!((eigenvalues[2].re - 0.5).abs() < 1e-9 && eigenvalues[2].im == 0., "Testing the real eigenvalue");
+
+    let general : Matrix<f64> = Matrix {
+        nb_lines : 4,
+        nb_columns : 4,
+        data : vec![4., 1., -2.,  2.,
+                    1., 2.,  0.,  1.,
+                   -2., 0.,  3., -2.,
+                    2., 1., -2., -1.]
+    };
+    let mut general_values : Vec<f64> = general.eigenvalues().unwrap().iter().map(|value| value.re).collect();
+    let mut symmetric_values = general.symmetric_eigen().unwrap().eigenvalues().to_vec();
+    general_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    symmetric_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (a, b) in general_values.iter().zip(symmetric_values) {
+        assert!((a - b).abs() < 1e-9, "Testing both solvers agree on a symmetric matrix");
+    }
+}
+
+#[test]
+fn views() {
+    let mut matrix = Matrix {
+        nb_lines : 3,
+        nb_columns : 3,
+        data : vec![1, 2, 3,
+                    4, 5, 6,
+                    7, 8, 9]
+    };
+
+    let block = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![5, 6,
+                    8, 9]
+    };
+    assert_eq!(matrix.view(1.., 1..).to_matrix(), block, "Testing a sub-matrix view");
+    assert_eq!(matrix.col(1).to_matrix(), Matrix { nb_lines : 3, nb_columns : 1, data : vec![2, 5, 8] }, "Testing a column view");
+    assert_eq!(matrix.t_view().to_matrix(), matrix.t(), "Testing the transposed view");
+    assert_eq!(matrix.t_view().view(1.., ..2).to_matrix(), Matrix { nb_lines : 2, nb_columns : 2, data : vec![2, 5, 3, 6] }, "Testing a view of a transposed view");
+    assert_eq!(matrix.view(..2, ..) * matrix.col(0), Matrix { nb_lines : 2, nb_columns : 1, data : vec![30, 66] }, "Testing the multiplication of views");
+    assert_eq!(matrix.view(1.., 1..) + &block, Matrix { nb_lines : 2, nb_columns : 2, data : vec![10, 12, 16, 18] }, "Testing the addition of a view and a matrix");
+    assert_eq!(matrix.row(2).sum_line(), Matrix { nb_lines : 1, nb_columns : 3, data : vec![7, 8, 9] }, "Testing a reduction on a view");
+    assert_eq!(matrix.view(..2, ..).try_add(&block), Err(MatrixError::Adding((2, 3), (2, 2))), "Testing try_add on views with different shapes");
+    assert_eq!(matrix.view(1.., 1..) - matrix.view(..2, ..2), Matrix { nb_lines : 2, nb_columns : 2, data : vec![4, 4, 4, 4] }, "Testing the subtraction of views");
+    assert_eq!(&block / matrix.view(1.., 1..), Matrix::ones(2, 2), "Testing the division of a matrix by a view");
+    assert_eq!(matrix.view(1.., 1..) % &block, Matrix::zeros(2, 2), "Testing the remainder of a view and a matrix");
+    assert_eq!(matrix.view(..2, ..).try_sub(&block), Err(MatrixError::Subtraction((2, 3), (2, 2))), "Testing try_sub on views with different shapes");
+    let mut copy = matrix.clone();
+    let mut window = copy.view_mut(1.., 1..);
+    window -= &block;
+    window += matrix.view(..2, ..2);
+    window &= &block;
+    assert_eq!(copy.view(1.., 1..).to_matrix(), Matrix { nb_lines : 2, nb_columns : 2, data : vec![5, 12, 32, 45] }, "Testing the compound assignments on a mutable view");
+
+    matrix.view_mut(..2, ..2).fill(0);
+    matrix.row_mut(2).apply(|a| a * 10);
+    assert_eq!(matrix, Matrix { nb_lines : 3, nb_columns : 3, data : vec![0, 0, 3, 0, 0, 6, 70, 80, 90] }, "Testing mutable views");
+}
+
+#[test]
+fn fixed_size_matrices() {
+    let a = SMatrix::new([[2, 5],
+                          [3, -7],
+                          [5, 0]]);
+    let b = SMatrix::new([[5, 4, 7, 0, -1],
+                          [3, -10, 5, 0, 4]]);
+    let product : SMatrix<i32, 3, 5> = a * b;
+    assert_eq!(Matrix::from(product), Matrix::from(a) * Matrix::from(b), "Testing the multiplication of fixed size matrices");
+    assert_eq!(SMatrix::<i32, 3, 2>::try_from(&Matrix::from(a)), Ok(a), "Testing the conversion from a Matrix");
+    assert_eq!(SMatrix::<i32, 2, 2>::try_from(Matrix::<i32>::zeros(2, 3)), Err(MatrixError::Conversion((2, 2), (2, 3))), "Testing the conversion from a Matrix with a different shape");
+    let c = SMatrix::new([[1, 1],
+                          [-2, 4],
+                          [0, 3]]);
+    assert_eq!(a - c, SMatrix::new([[1, 4], [5, -11], [5, -3]]), "Testing the subtraction of fixed size matrices");
+    let (borrowed_a, borrowed_c) = (&a, &c);
+    assert_eq!(borrowed_a - borrowed_c, a + -c, "Testing the subtraction of borrowed fixed size matrices");
+    let mut d = a;
+    d -= c;
+    assert_eq!(d, a - c, "Testing the subtraction assignment of fixed size matrices");
+
+    let m4 : SMatrix4<f64> = SMatrix::new([[4., 1., -2.,  2.],
+                                          [1., 2.,  0.,  1.],
+                                          [-2., 0., 3., -2.],
+                                          [2., 1., -2., -1.]]);
+    let dynamic = Matrix::from(m4);
+    assert!((m4.det() - dynamic.det().unwrap()).abs() < 1e-9, "Testing the 4 x 4 determinant");
+    assert_close(&Matrix::from(m4 * m4.inverse().unwrap()), &Matrix::identity(4), "Testing the 4 x 4 inverse");
+
+    let m3 : SMatrix3<f64> = SMatrix::new([[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]]);
+    assert!((m3.det() + 16.).abs() < 1e-9, "Testing the 3 x 3 determinant");
+    assert_close(&Matrix::from(m3.inverse().unwrap() * m3), &Matrix::identity(3), "Testing the 3 x 3 inverse");
+    assert_eq!(SMatrix2::new([[1., 2.], [2., 4.]]).inverse(), Err(MatrixError::Singular), "Testing the inverse of a singular 2 x 2 matrix");
+}
+
+#[test]
+fn blocked_multiplication() {
+    let (m, k, n) = (70, 150, 130);
+    let a = Matrix {
+        nb_lines : m,
+        nb_columns : k,
+        data : (0..m * k).map(|x| (x as i64 * 7) % 11 - 5).collect(),
+    };
+    let b = Matrix {
+        nb_lines : k,
+        nb_columns : n,
+        data : (0..k * n).map(|x| (x as i64 * 3) % 13 - 6).collect(),
+    };
+    let mut naive = Matrix::<i64>::zeros(m, n);
+    for i in 0..m {
+        for j in 0..n {
+            for p in 0..k {
+                naive[i][j] += a[i][p] * b[p][j];
+            }
+        }
+    }
+    assert_eq!(&a * &b, naive, "Testing the blocked product against the naive one");
+
+    let mut c = Matrix::<i64>::ones(m, n);
+    gemm(2, &a, &b, -1, &mut c).unwrap();
+    assert_eq!(c, naive.clone() * 2 + Matrix::ones(m, n).map(|x : i64| -x), "Testing gemm with alpha and beta");
+    assert_eq!(gemm(1, &a, &b, 0, &mut Matrix::zeros(n, m)), Err(MatrixError::Accumulation((n, m), (m, n))), "Testing gemm with a wrongly shaped output");
+
+    let mut out = Matrix::new();
+    mul_into(b.t_view(), a.t_view(), &mut out).unwrap();
+    assert_eq!(out, naive.t(), "Testing mul_into with transposed views");
+    assert_eq!(mul_into(&a, &a, &mut out), Err(MatrixError::Multiplication((m, k), (m, k))), "Testing mul_into with incompatible shapes");
+}
+
+#[test]
+fn large_elementwise_operations() {
+    let (m, n) = (300, 200);
+    let matrix = Matrix {
+        nb_lines : m,
+        nb_columns : n,
+        data : (0..m * n).map(|x| (x % 17) as i64).collect(),
+    };
+    let line = Matrix {
+        nb_lines : 1,
+        nb_columns : n,
+        data : (0..n as i64).collect(),
+    };
+
+    let shifted = matrix.clone().add_to_lines(&line);
+    for i in [0, 150, 299] {
+        for j in [0, 77, 199] {
+            assert_eq!(shifted[i][j], matrix[i][j] + j as i64, "Testing add_to_lines on a large matrix");
+        }
+    }
+    let sums = matrix.clone().sum_line();
+    for j in [0, 77, 199] {
+        assert_eq!(sums[0][j], (0..m).map(|i| matrix[i][j]).sum::<i64>(), "Testing sum_line on a large matrix");
+    }
+    assert_eq!(&matrix + &matrix, matrix.clone().map(|x| 2 * x), "Testing addition and map on a large matrix");
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_functionalities() {
+    use rayon::prelude::*;
+
+    let mut matrix = Matrix::<i32>::zeros(4, 3);
+    matrix.par_rows_mut().enumerate().for_each(|(i, line)| line.iter_mut().for_each(|a| *a = i as i32));
+    assert_eq!(matrix.t()[0], [0, 1, 2, 3], "Testing par_rows_mut");
+    assert_eq!(matrix.clone().par_map(|a| a + 1), matrix.map(|a| a + 1), "Testing par_map");
+
+    let (m, n) = (300, 200);
+    let large = Matrix {
+        nb_lines : m,
+        nb_columns : n,
+        data : (0..m * n).map(|x| (x % 17) as i64 - 8).collect(),
+    };
+    let line = Matrix {
+        nb_lines : 1,
+        nb_columns : n,
+        data : (0..n as i64).collect(),
+    };
+    let column = Matrix {
+        nb_lines : m,
+        nb_columns : 1,
+        data : (0..m as i64).collect(),
+    };
+    assert_eq!(large.par_add(&large), &large + &large, "Testing par_add");
+    assert_eq!(large.par_sub(&large), &large - &large, "Testing par_sub");
+    assert_eq!(large.par_hadamard(&large), &large & &large, "Testing par_hadamard");
+    assert_eq!(large.try_par_add(&line), Err(MatrixError::Adding((m, n), (1, n))), "Testing par_add with different shapes");
+    assert_eq!(large.clone().par_add_to_lines(&line), large.clone().add_to_lines(&line), "Testing par_add_to_lines");
+    assert_eq!(large.clone().par_add_to_columns(&column), large.clone().add_to_columns(&column), "Testing par_add_to_columns");
+    assert_eq!(large.clone().par_sum_line(), large.clone().sum_line(), "Testing par_sum_line");
+    assert_eq!(large.par_mul(large.t()), &large * &large.t(), "Testing par_mul");
+    assert_eq!(large.try_par_mul(&large), Err(MatrixError::Multiplication((m, n), (m, n))), "Testing par_mul with incompatible shapes");
+}
+
+#[test]
+fn binary_checkpoints() {
+    let weights = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![0.5, -1.25, 3., 1e-8, f64::MAX, -0.],
+    };
+    let bias = Matrix {
+        nb_lines : 1,
+        nb_columns : 3,
+        data : vec![1., 2., 3.],
+    };
+
+    let mut bytes = Vec::new();
+    weights.write_binary(&mut bytes).unwrap();
+    assert_eq!(&bytes[..4], b"RMAT", "Testing the magic header");
+    assert_eq!(Matrix::<f64>::read_binary(bytes.as_slice()).unwrap(), weights, "Testing the round trip of one matrix");
+    assert!(matches!(Matrix::<f32>::read_binary(bytes.as_slice()), Err(FormatError::DtypeMismatch(..))), "Testing the reading with another element type");
+    bytes[40] ^= 1; //inside the data
+    assert!(matches!(Matrix::<f64>::read_binary(bytes.as_slice()), Err(FormatError::Checksum(..))), "Testing the detection of corrupted data");
+
+    let mut bytes = Vec::new();
+    Matrix::write_collection(&mut bytes, [("weights", &weights), ("bias", &bias)]).unwrap();
+    let collection = Matrix::<f64>::read_collection(bytes.as_slice()).unwrap();
+    assert_eq!(collection, vec![("weights".to_string(), weights), ("bias".to_string(), bias)], "Testing the round trip of a named collection");
+    bytes[10..14].copy_from_slice(&u32::MAX.to_le_bytes()); //length of the first name
+    assert!(matches!(Matrix::<f64>::read_collection(bytes.as_slice()), Err(FormatError::InvalidHeader(..))), "Testing the rejection of a huge name length");
+    assert!(matches!(Matrix::write_collection(&mut Vec::new(), [("a".repeat(1 << 17), &Matrix::<f64>::zeros(1, 1))]), Err(FormatError::InvalidData(..))), "Testing the rejection of a too long name");
+}
+
+#[test]
+fn numpy_files() {
+    let matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![1i32, -2, 3, 4, 5, -6],
+    };
+    let mut bytes = Vec::new();
+    matrix.write_npy_to(&mut bytes).unwrap();
+    assert_eq!(&bytes[..6], b"\x93NUMPY", "Testing the magic string");
+    assert_eq!((bytes.len() - 6 * 4) % 64, 0, "Testing the alignment of the header");
+    assert_eq!(Matrix::<i32>::read_npy_from(bytes.as_slice()).unwrap(), matrix, "Testing the .npy round trip");
+    assert!(matches!(Matrix::<f64>::read_npy_from(bytes.as_slice()), Err(FormatError::DtypeMismatch(..))), "Testing the reading with another element type");
+
+    let npy = |header : &str, data : &[u8]| {
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend((header.len() as u16).to_le_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(data);
+        bytes
+    };
+    let fortran = npy("{'descr': '<f8', 'fortran_order': True, 'shape': (2, 2), }\n", &[1f64, 3., 2., 4.].iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<u8>>());
+    assert_eq!(Matrix::<f64>::read_npy_from(fortran.as_slice()).unwrap().data, vec![1., 2., 3., 4.], "Testing the reading of Fortran order");
+    let big_endian = npy("{'descr': '>i8', 'fortran_order': False, 'shape': (1, 2), }\n", &[7i64, -1].iter().flat_map(|x| x.to_be_bytes()).collect::<Vec<u8>>());
+    assert_eq!(Matrix::<i64>::read_npy_from(big_endian.as_slice()).unwrap().data, vec![7, -1], "Testing the reading of big-endian data");
+    let vector = npy("{'descr': '|u1', 'fortran_order': False, 'shape': (3,), }\n", &[1, 2, 3]);
+    assert!(matches!(Matrix::<u8>::read_npy_from(vector.as_slice()), Err(FormatError::NotTwoDimensional(shape)) if shape == vec![3]), "Testing the rejection of 1-D arrays");
+    let mut forged = b"\x93NUMPY\x02\x00".to_vec();
+    forged.extend(u32::MAX.to_le_bytes());
+    assert!(matches!(Matrix::<u8>::read_npy_from(forged.as_slice()), Err(FormatError::InvalidHeader(_))), "Testing the rejection of a forged header length");
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz_archives() {
+    let mask = Matrix {
+        nb_lines : 1,
+        nb_columns : 2,
+        data : vec![true, false],
+    };
+    for compressed in [false, true] {
+        let mut archive = std::io::Cursor::new(Vec::new());
+        Matrix::write_npz_to(&mut archive, [("a", &mask), ("b", &mask)], compressed).unwrap();
+        archive.set_position(0);
+        let matrices = Matrix::<bool>::read_npz_from(archive).unwrap();
+        assert_eq!(matrices, vec![("a".to_string(), mask.clone()), ("b".to_string(), mask.clone())], "Testing the .npz round trip");
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_serialization() {
+    let matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![1, 2, 3, 4],
+    };
+    let json = serde_json::to_string(&matrix).unwrap();
+    assert_eq!(serde_json::from_str::<Matrix<i32>>(&json).unwrap(), matrix, "Testing the serde round trip");
+    assert!(serde_json::from_str::<Matrix<i32>>(r#"{"nb_lines":2,"nb_columns":2,"data":[1,2,3]}"#).is_err(), "Testing the rejection of data not matching the shape");
+}
+
+#[test]
+fn csv_files() {
+    let text = "x;y;label\n1.5;-2;0\n\n3;;1\n";
+    let options = CsvOptions {
+        delimiter : ';',
+        header : true,
+        missing : MissingValues::Fill(0.),
+        ..CsvOptions::default()
+    };
+    let matrix = Matrix::<f64>::from_csv(text.as_bytes(), &options).unwrap();
+    assert_eq!(matrix, Matrix { nb_lines : 2, nb_columns : 3, data : vec![1.5, -2., 0., 3., 0., 1.] }, "Testing the reading with a header, a blank line and a filled value");
+    let nan = CsvOptions { missing : MissingValues::NaN, ..options.clone() };
+    assert!(Matrix::<f64>::from_csv(text.as_bytes(), &nan).unwrap()[1][1].is_nan(), "Testing the NaN missing value policy");
+    let error = CsvOptions { missing : MissingValues::Error, ..options.clone() };
+    assert!(matches!(Matrix::<f64>::from_csv(text.as_bytes(), &error), Err(FormatError::MissingValue(4, 2))), "Testing the error missing value policy");
+    assert!(matches!(Matrix::<i32>::from_csv("1,2\n3,x\n".as_bytes(), &CsvOptions::default()), Err(FormatError::Parse(2, 2, field)) if field == "x"), "Testing the report of unparsable fields");
+    assert!(matches!(Matrix::<i32>::from_csv("1,2\n3\n".as_bytes(), &CsvOptions::default()), Err(FormatError::InvalidData(_))), "Testing the rejection of ragged lines");
+    let quoted = "\"1\", 2 ,\"\"\n\t\"-3\" ,\"4\",5\n";
+    let fill = CsvOptions { missing : MissingValues::Fill(0), ..CsvOptions::default() };
+    assert_eq!(Matrix::<i32>::from_csv(quoted.as_bytes(), &fill).unwrap().data, vec![1, 2, 0, -3, 4, 5], "Testing the reading of quoted fields");
+    assert!(matches!(Matrix::<i32>::from_csv("\"1,2\",3\n".as_bytes(), &CsvOptions::default()), Err(FormatError::Parse(1, 1, field)) if field == "1,2"), "Testing a quoted field containing the delimiter");
+    assert!(matches!(Matrix::<i32>::from_csv("\"1\n2\",3\n".as_bytes(), &CsvOptions::default()), Err(FormatError::InvalidData(_))), "Testing the rejection of a quoted field with a line break");
+    assert!(matches!(Matrix::<i32>::from_csv("\"1\"2,3\n".as_bytes(), &CsvOptions::default()), Err(FormatError::InvalidData(_))), "Testing the rejection of characters after a closing quote");
+    let tabs = CsvOptions { delimiter : '\t', missing : MissingValues::Fill(0), ..CsvOptions::default() };
+    assert_eq!(Matrix::<i32>::from_csv("1\t\t3\n".as_bytes(), &tabs).unwrap().data, vec![1, 0, 3], "Testing an empty field with a blank delimiter");
+
+    let mut bytes = Vec::new();
+    let options = CsvOptions {
+        header : true,
+        column_names : vec!["x".to_string(), "y".to_string(), "label".to_string()],
+        precision : Some(2),
+        ..CsvOptions::default()
+    };
+    matrix.to_csv(&mut bytes, &options).unwrap();
+    assert_eq!(String::from_utf8(bytes.clone()).unwrap(), "x,y,label\n1.50,-2.00,0.00\n3.00,0.00,1.00\n", "Testing the writing with a header and a precision");
+    assert_eq!(Matrix::<f64>::from_csv(bytes.as_slice(), &options).unwrap(), matrix, "Testing the csv round trip");
+
+    let mut bytes = Vec::new();
+    let options = CsvOptions {
+        header : true,
+        column_names : vec!["a,b".to_string(), "say \"hi\"".to_string(), "c".to_string()],
+        ..CsvOptions::default()
+    };
+    matrix.to_csv(&mut bytes, &options).unwrap();
+    assert!(String::from_utf8(bytes).unwrap().starts_with("\"a,b\",\"say \"\"hi\"\"\",c\n"), "Testing the quoting of the column names");
+    let options = CsvOptions { column_names : vec!["a\nb".to_string(), "c".to_string(), "d".to_string()], ..options };
+    assert!(matches!(matrix.to_csv(Vec::new(), &options), Err(FormatError::InvalidData(_))), "Testing the rejection of column names with line breaks");
+}
+
+#[test]
+fn matrix_market_files() {
+    let dense = "%%MatrixMarket matrix array real symmetric\n% a comment\n2 2\n1.5\n-2\n4\n";
+    let matrix = Matrix::<f64>::from_matrix_market(dense.as_bytes()).unwrap();
+    assert_eq!(matrix, Matrix { nb_lines : 2, nb_columns : 2, data : vec![1.5, -2., -2., 4.] }, "Testing the reading of a symmetric array");
+
+    let sparse = "%%MatrixMarket matrix coordinate integer general\n2 3 2\n1 3 7\n2 1 -1\n";
+    let matrix = Matrix::<i32>::from_matrix_market(sparse.as_bytes()).unwrap();
+    assert_eq!(matrix, Matrix { nb_lines : 2, nb_columns : 3, data : vec![0, 0, 7, -1, 0, 0] }, "Testing the reading of a general coordinate file");
+    let pattern = "%%MatrixMarket matrix coordinate pattern symmetric\n2 2 1\n2 1\n";
+    assert_eq!(Matrix::<u8>::from_matrix_market(pattern.as_bytes()).unwrap().data, vec![0, 1, 1, 0], "Testing the reading of a symmetric pattern file");
+    assert!(matches!(Matrix::<i32>::from_matrix_market(dense.as_bytes()), Err(FormatError::DtypeMismatch(..))), "Testing the reading of real values as integers");
+    assert!(matches!(Matrix::<i32>::from_matrix_market("%%MatrixMarket matrix coordinate integer general\n2 2 1\n3 1 5\n".as_bytes()), Err(FormatError::InvalidData(_))), "Testing the rejection of entries outside of the matrix");
+
+    for format in [MatrixMarketFormat::Array, MatrixMarketFormat::Coordinate] {
+        let mut bytes = Vec::new();
+        matrix.to_matrix_market(&mut bytes, format).unwrap();
+        assert_eq!(Matrix::<i32>::from_matrix_market(bytes.as_slice()).unwrap(), matrix, "Testing the Matrix Market round trip");
+    }
+
+    let duplicates = "%%MatrixMarket matrix coordinate integer general\n2 2 3\n1 2 3\n2 1 1\n1 2 4\n";
+    let dense = Matrix::<i32>::from_matrix_market(duplicates.as_bytes()).unwrap();
+    assert_eq!(dense.data, vec![0, 7, 1, 0], "Testing the sum of duplicate entries");
+    assert_eq!(CooMatrix::<i32>::from_matrix_market(duplicates.as_bytes()).unwrap().to_dense(), dense, "Testing the same rule for duplicates in the COO path");
+
+    let symmetric = Matrix::<f64>::from_matrix_market("%%MatrixMarket matrix array real symmetric\n3 3\n1\n2\n0\n5\n-1\n9\n".as_bytes()).unwrap();
+    for format in [MatrixMarketFormat::Array, MatrixMarketFormat::Coordinate] {
+        let mut bytes = Vec::new();
+        symmetric.to_matrix_market_symmetric(&mut bytes, format).unwrap();
+        assert!(String::from_utf8(bytes.clone()).unwrap().lines().next().unwrap().ends_with("real symmetric"), "Testing the symmetric banner");
+        assert_eq!(Matrix::<f64>::from_matrix_market(bytes.as_slice()).unwrap(), symmetric, "Testing the symmetric round trip");
+    }
+    let coo = CooMatrix::<f64>::from_matrix_market("%%MatrixMarket matrix coordinate real symmetric\n3 3 2\n2 1 4\n3 3 1\n".as_bytes()).unwrap();
+    let mut bytes = Vec::new();
+    coo.to_matrix_market_symmetric(&mut bytes).unwrap();
+    assert_eq!(CooMatrix::<f64>::from_matrix_market(bytes.as_slice()).unwrap().to_dense(), coo.to_dense(), "Testing the symmetric round trip of a COO matrix");
+    assert!(matches!(matrix.to_matrix_market_symmetric(&mut Vec::new(), MatrixMarketFormat::Array), Err(FormatError::InvalidData(_))), "Testing the rejection of a non symmetric matrix");
+
+    let huge_array = "%%MatrixMarket matrix array integer general\n4294967296 4294967296\n";
+    assert!(matches!(Matrix::<i32>::from_matrix_market(huge_array.as_bytes()), Err(FormatError::InvalidHeader(_))), "Testing the rejection of an overflowing array shape");
+    let huge_coordinate = format!("%%MatrixMarket matrix coordinate integer general\n2 2 {}\n1 1 1\n", usize::MAX);
+    assert!(matches!(CooMatrix::<i32>::from_matrix_market(huge_coordinate.as_bytes()), Err(FormatError::InvalidData(_))), "Testing a number of entries greater than the file");
+}
+
+#[test]
+fn sparse_matrices() {
+    let mut coo = CooMatrix::new(3, 4);
+    coo.push(0, 1, 2.);
+    coo.push(2, 3, -1.);
+    coo.push(1, 0, 4.);
+    coo.push(0, 1, 1.); //summed with the first element
+    let dense = Matrix {
+        nb_lines : 3,
+        nb_columns : 4,
+        data : vec![0., 3., 0.,  0.,
+                    4., 0., 0.,  0.,
+                    0., 0., 0., -1.]
+    };
+    let csr = coo.to_csr();
+    let csc = coo.to_csc();
+    assert_eq!(csr.nnz(), 3, "Testing the merging of duplicated elements");
+    assert_eq!(csr.to_dense(), dense, "Testing the CSR to dense conversion");
+    assert_eq!(csc.to_dense(), dense, "Testing the CSC to dense conversion");
+    assert_eq!(coo.to_dense(), dense, "Testing the COO to dense conversion");
+    assert_eq!(CsrMatrix::from_dense(&dense, 0.), csr, "Testing the dense to CSR conversion");
+    assert_eq!(CscMatrix::from_dense(&dense, 0.), csc, "Testing the dense to CSC conversion");
+    assert_eq!(CsrMatrix::from_dense(&dense, 3.5).nnz(), 1, "Testing the tolerance of the dense conversion");
+    assert_eq!(csr.to_csc(), csc, "Testing the CSR to CSC conversion");
+    assert_eq!(csc.to_csr(), csr, "Testing the CSC to CSR conversion");
+    assert_eq!(csr.get(0, 1), Some(&3.), "Testing the access to a stored element");
+    assert_eq!(csr.get(0, 0), None, "Testing the access to a missing element");
+    assert_eq!(csr.clone().t().to_dense(), dense.t(), "Testing the transpose");
+
+    let mut rng = rand::thread_rng();
+    let lhs = Matrix::new_rand(5, 3, &mut rng, rand::distributions::Uniform::new(-1., 1.));
+    let rhs = Matrix::new_rand(4, 2, &mut rng, rand::distributions::Uniform::new(-1., 1.));
+    assert_close(&(&csr * &rhs), &(&dense * &rhs), "Testing the CSR * dense product");
+    assert_close(&(&csc * &rhs), &(&dense * &rhs), "Testing the CSC * dense product");
+    assert_close(&(&lhs * &csr), &(&lhs * &dense), "Testing the dense * CSR product");
+    assert_close(&(&lhs * &csc), &(&lhs * &dense), "Testing the dense * CSC product");
+    let square = CsrMatrix::from_dense(&(&dense.t() * &dense), 0.);
+    assert_close(&(&csr * &square).to_dense(), &(&dense * &square.to_dense()), "Testing the CSR * CSR product");
+    assert_close(&(&csc * &square.to_csc()).to_dense(), &(&dense * &square.to_dense()), "Testing the CSC * CSC product");
+    assert_eq!((&csr + &csr).to_dense(), &dense + &dense, "Testing the CSR addition");
+    assert_eq!((&csc + &csc).to_dense(), &dense + &dense, "Testing the CSC addition");
+    assert_eq!(csr.try_mul(&csr), Err(MatrixError::Multiplication((3, 4), (3, 4))), "Testing the product of incompatible shapes");
+    assert_eq!(csr.try_mul_dense(&lhs).err(), Some(MatrixError::Multiplication((3, 4), (5, 3))), "Testing the sparse * dense product of incompatible shapes");
+}
+
+#[test]
+fn line_and_column_iterators() {
+    let mut matrix = Matrix {
+        nb_lines : 3,
+        nb_columns : 2,
+        data : vec![1, 2,
+                    3, 4,
+                    5, 6]
+    };
+    assert_eq!(matrix.rows().len(), 3, "Testing the length of rows");
+    assert_eq!(matrix.rows().collect::<Vec<&[i32]>>(), vec![&[1, 2][..], &[3, 4], &[5, 6]], "Testing rows");
+    assert_eq!(matrix.rows().rev().nth(1), Some(&[3, 4][..]), "Testing rows from the end");
+    assert_eq!(matrix.cols().map(|column| column.sum()).collect::<Vec<i32>>(), vec![9, 12], "Testing cols");
+    assert_eq!(matrix.cols().next_back().unwrap().into_iter().copied().collect::<Vec<i32>>(), vec![2, 4, 6], "Testing cols from the end");
+    assert_eq!(matrix.diagonal().copied().collect::<Vec<i32>>(), vec![1, 4], "Testing the diagonal of a non square matrix");
+    let mut indexed = matrix.indexed_iter();
+    assert_eq!(indexed.len(), 6, "Testing the length of indexed_iter");
+    assert_eq!(indexed.nth(3), Some(((1, 1), &4)), "Testing indexed_iter");
+    assert_eq!(indexed.next_back(), Some(((2, 1), &6)), "Testing indexed_iter from the end");
+
+    let mut rows = matrix.rows_mut();
+    rows.next_back().unwrap()[0] = 0;
+    rows.next().unwrap()[1] = 0;
+    assert_eq!(rows.len(), 1, "Testing the length of rows_mut");
+    assert_eq!(matrix.data, vec![1, 0, 3, 4, 0, 6], "Testing rows_mut");
+    let mut rows = matrix.into_rows();
+    assert_eq!(rows.next_back(), Some(vec![0, 6]), "Testing into_rows from the end");
+    assert_eq!(rows.collect::<Vec<Vec<i32>>>(), vec![vec![1, 0], vec![3, 4]], "Testing into_rows");
+}
+
+#[test]
+fn mini_batches() {
+    let features = Matrix {
+        nb_lines : 5,
+        nb_columns : 2,
+        data : (0..10).collect(),
+    };
+    let labels = Matrix {
+        nb_lines : 5,
+        nb_columns : 1,
+        data : (0..5).collect(),
+    };
+    let batches = features.batches(2).map(|batch| batch.to_matrix()).collect::<Vec<Matrix<i32>>>();
+    assert_eq!(batches.iter().map(|batch| batch.lines()).collect::<Vec<usize>>(), vec![2, 2, 1], "Testing the sizes of the batches");
+    assert_eq!(batches[1].data, vec![4, 5, 6, 7], "Testing the content of a batch");
+    assert_eq!(features.batches(2).drop_last().len(), 2, "Testing drop_last");
+    assert_eq!(features.batches(2).next_back().unwrap().to_matrix().data, vec![8, 9], "Testing the batches from the end");
+
+    let mut rng = rand::thread_rng();
+    let shuffled = features.shuffled_batches(2, &mut rng);
+    let mut permutation = shuffled.permutation().to_vec();
+    let lines = shuffled.flat_map(|batch| batch.into_rows()).map(|line| line[0] / 2).collect::<Vec<i32>>();
+    assert_eq!(lines, permutation.iter().map(|i| *i as i32).collect::<Vec<i32>>(), "Testing the order of the shuffled lines");
+    permutation.sort_unstable();
+    assert_eq!(permutation, vec![0, 1, 2, 3, 4], "Testing that every line is used once");
+
+    let mut shuffled = features.shuffled_batches(2, &mut rng);
+    let expected = features.chose_lines_by_index(shuffled.permutation().to_vec());
+    let mut lent = Vec::new();
+    let mut allocation = None;
+    while let Some(batch) = shuffled.next_batch() {
+        assert!(allocation.is_none_or(|pointer| pointer == batch.data.as_ptr()), "Testing that the buffer of next_batch is reused");
+        allocation = Some(batch.data.as_ptr());
+        lent.extend_from_slice(&batch.data);
+    }
+    assert_eq!(lent, expected.data, "Testing the lines lent by next_batch");
+    let mut paired = features.shuffled_paired_batches(&labels, 2, &mut rng);
+    while let Some((x, y)) = paired.next_batch() {
+        assert_eq!(x.rows().map(|line| line[0] / 2).collect::<Vec<i32>>(), y.data, "Testing that next_batch shuffles features and labels together");
+    }
+
+    for (x, y) in features.shuffled_paired_batches(&labels, 2, &mut rng).drop_last() {
+        assert_eq!(x.lines(), 2, "Testing drop_last on paired batches");
+        assert_eq!(x.rows().map(|line| line[0] / 2).collect::<Vec<i32>>(), y.data, "Testing that features and labels are shuffled together");
+    }
+    assert_eq!(features.paired_batches(&labels, 3).map(|(x, y)| (x.lines(), y.lines())).collect::<Vec<(usize, usize)>>(), vec![(3, 3), (2, 2)], "Testing the paired batches");
+    assert_eq!(features.try_paired_batches(&Matrix::<i32>::zeros(4, 1), 2).err(), Some(MatrixError::Pairing((5, 2), (4, 1))), "Testing the pairing of matrices with different number of lines");
+}
+
+#[test]
+fn activations_and_losses() {
+    let x = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![-2., -0.5, 0.3,
+                     1., 2.5, -40.]
+    };
+    let h = 1e-6;
+    let shifted = |delta : f64| x.clone().map(move |x| x + delta);
+    let numerical = |f : &dyn Fn(&Matrix<f64>) -> Matrix<f64>| (f(&shifted(h)) - f(&shifted(-h))) / (2. * h);
+    let check = |derivative : Matrix<f64>, numerical : Matrix<f64>, message : &str| {
+        for (a, b) in derivative.into_iter().zip(numerical) {
+            assert!((a - b).abs() < 1e-6, "{} : {} != {}", message, a, b);
+        }
+    };
+    check(nn::sigmoid_derivative(&x), numerical(&nn::sigmoid), "Testing the derivative of sigmoid");
+    check(nn::tanh_derivative(&x), numerical(&nn::tanh), "Testing the derivative of tanh");
+    check(nn::relu_derivative(&x), numerical(&nn::relu), "Testing the derivative of relu");
+    check(nn::leaky_relu_derivative(&x, 0.1), numerical(&|x| nn::leaky_relu(x, 0.1)), "Testing the derivative of leaky relu");
+    check(nn::gelu_derivative(&x), numerical(&nn::gelu), "Testing the derivative of gelu");
+    assert_eq!(nn::relu(&x).data, vec![0., 0., 0.3, 1., 2.5, 0.], "Testing relu");
+    assert!((nn::gelu(&x)[1][0] - 0.841192).abs() < 1e-6, "Testing gelu");
+
+    let logits = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![1000., 1001., 1002.,
+                    -1., 0., 1.]
+    };
+    let probabilities = nn::softmax(&logits);
+    assert!(probabilities.rows().all(|line| (line.iter().sum::<f64>() - 1.).abs() < 1e-12), "Testing that the lines of softmax sum to one");
+    assert_close(&nn::log_softmax(&logits).map(f64::exp), &probabilities, "Testing log_softmax against softmax");
+    let gradient = Matrix::new_rand(2, 3, &mut rand::thread_rng(), rand::distributions::Uniform::new(-1., 1.));
+    let through_log = nn::log_softmax_backward(&nn::log_softmax(&logits), &(gradient.clone() & probabilities.clone()));
+    assert_close(&nn::softmax_backward(&probabilities, &gradient), &through_log, "Testing the backward passes of softmax and log_softmax");
+    assert_eq!(nn::try_softmax_backward(&probabilities, &gradient.t()).err(), Some(MatrixError::Backward(probabilities.shape(), gradient.t().shape())), "Testing softmax_backward with a gradient of another shape");
+
+    let targets = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![0., 0., 1.,
+                    1., 0., 0.]
+    };
+    let (loss, gradient) = nn::cross_entropy(&logits, &targets);
+    assert!((loss - (0.40760596 + 2.40760596) / 2.).abs() < 1e-6, "Testing the cross-entropy");
+    assert_close(&gradient, &((probabilities - &targets) / 2.), "Testing the gradient of the cross-entropy");
+    let (loss, gradient) = nn::mse(&x, &Matrix::zeros(2, 3));
+    assert!((loss - x.clone().map(|x| x * x).into_iter().sum::<f64>() / 6.).abs() < 1e-9, "Testing the mean squared error");
+    assert_close(&gradient, &(x.clone() / 3.), "Testing the gradient of the mean squared error");
+    let predictions = nn::sigmoid(&x);
+    let (loss, gradient) = nn::binary_cross_entropy(&predictions, &targets);
+    let numerical_loss = |delta : f64| {
+        let mut shifted = predictions.clone();
+        shifted[0][1] += delta;
+        nn::binary_cross_entropy(&shifted, &targets).0
+    };
+    assert!(loss > 0., "Testing the binary cross-entropy");
+    assert!((gradient[0][1] - (numerical_loss(h) - numerical_loss(-h)) / (2. * h)).abs() < 1e-5, "Testing the gradient of the binary cross-entropy");
+    assert_eq!(nn::try_mse(&x, &targets.t()).err(), Some(MatrixError::Loss((2, 3), (3, 2))), "Testing a loss with different shapes");
+}
+
+#[test]
+fn automatic_differentiation() {
+    let mut rng = rand::thread_rng();
+    let uniform = rand::distributions::Uniform::new(-1., 1.);
+    let inputs = Matrix::new_rand(4, 3, &mut rng, uniform);
+    let weights = Matrix::new_rand(3, 2, &mut rng, uniform);
+    let bias = Matrix::new_rand(1, 2, &mut rng, uniform);
+    let output = Matrix::new_rand(2, 1, &mut rng, uniform);
+    let loss = |weights : &Matrix<f64>, bias : &Matrix<f64>| {
+        let tape = Tape::new();
+        let x = tape.variable(inputs.clone());
+        let w = tape.variable(weights.clone());
+        let b = tape.variable(bias.clone());
+        let v = tape.variable(output.clone());
+        let hidden = (w.t() * x.t()).t().add_to_lines(b).map(Function::Tanh);
+        let prediction = (hidden & hidden) + hidden.map(Function::Sigmoid);
+        let value = (prediction * v).map(Function::Square).sum_line();
+        let gradients = value.backward();
+        let result = value.with_value(|value| value[0][0]);
+        (result, gradients.get(w).cloned(), gradients.get(b).cloned(), tape.len())
+    };
+    let (_, gradient_w, gradient_b, nb_nodes) = loss(&weights, &bias);
+    assert_eq!(nb_nodes, 16, "Testing the number of recorded nodes");
+    let h = 1e-6;
+    for (parameter, gradient) in [(0, gradient_w.unwrap()), (1, gradient_b.unwrap())] {
+        let value = if parameter == 0 { &weights } else { &bias };
+        assert_eq!(gradient.shape(), value.shape(), "Testing the shape of the gradient");
+        for i in 0..value.lines() {
+            for j in 0..value.columns() {
+                let (mut plus, mut minus) = (value.clone(), value.clone());
+                plus[i][j] += h;
+                minus[i][j] -= h;
+                let numerical = if parameter == 0 {
+                    (loss(&plus, &bias).0 - loss(&minus, &bias).0) / (2. * h)
+                }
+                else {
+                    (loss(&weights, &plus).0 - loss(&weights, &minus).0) / (2. * h)
+                };
+                assert!((gradient[i][j] - numerical).abs() < 1e-6, "Testing the gradient against finite differences : {} != {}", gradient[i][j], numerical);
+            }
+        }
+    }
+
+    let tape = Tape::new();
+    let a = tape.variable(Matrix::<f64>::ones(2, 3));
+    let b = tape.variable(Matrix::<f64>::ones(2, 3));
+    let unused = tape.variable(Matrix::<f64>::ones(1, 1));
+    assert_eq!(a.try_mul(b).err(), Some(MatrixError::Multiplication((2, 3), (2, 3))), "Testing the product of incompatible variables");
+    let gradients = (a + b).backward();
+    assert!(gradients.get(unused).is_none(), "Testing the gradient of an unused variable");
+    assert_eq!(gradients.get(a), Some(&Matrix::ones(2, 3)), "Testing the gradient of an addition");
+    let value = a.value_cloned();
+    let transposed = a.t();
+    assert_eq!(transposed.value_cloned(), value.t(), "Testing the recording of an operation while a value is kept");
+    assert_eq!(transposed.with_value(|value| value.shape()), (3, 2), "Testing the borrow of a value");
+}
+
+#[test]
+#[should_panic(expected = "another tape")]
+fn gradients_of_another_tape_panic() {
+    let tape = Tape::new();
+    let a = tape.variable(Matrix::<f64>::ones(1, 1));
+    let other = Tape::new();
+    let b = other.variable(Matrix::<f64>::ones(1, 1));
+    a.map(Function::Square).backward().get(b);
+}
+
+#[test]
+fn weight_initializers() {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let weights = Matrix::<f64>::xavier_uniform(30, 20, &mut rng);
+    let limit = (6f64 / 50.).sqrt();
+    assert!(weights.into_iter().all(|w| w.abs() <= limit), "Testing the bounds of xavier_uniform");
+    assert!(Matrix::<f64>::he_uniform(24, 10, &mut rng).into_iter().all(|w| w.abs() <= 0.5), "Testing the bounds of he_uniform");
+    let weights = Matrix::<f64>::he_normal(200, 100, &mut rng);
+    let variance = weights.into_iter().map(|w| w * w).sum::<f64>() / 20000.;
+    assert!((variance - 0.01).abs() < 0.001, "Testing the variance of he_normal : {}", variance);
+    let weights = Matrix::<f64>::lecun_normal(100, 200, &mut rng);
+    let variance = weights.into_iter().map(|w| w * w).sum::<f64>() / 20000.;
+    assert!((variance - 0.01).abs() < 0.001, "Testing the variance of lecun_normal : {}", variance);
+    let weights = Matrix::<f64>::xavier_normal(150, 50, &mut rng);
+    let variance = weights.into_iter().map(|w| w * w).sum::<f64>() / 7500.;
+    assert!((variance - 0.01).abs() < 0.001, "Testing the variance of xavier_normal : {}", variance);
+
+    let tall = Matrix::<f64>::orthogonal(6, 4, 2., &mut rng);
+    assert_close(&(tall.t() * &tall), &(Matrix::identity(4) * 4.), "Testing the orthogonal columns");
+    let wide = Matrix::<f64>::orthogonal(3, 5, 1., &mut rng);
+    assert_close(&(&wide * &wide.t()), &Matrix::identity(3), "Testing the orthogonal lines");
+
+    let first = Matrix::<f32>::orthogonal(4, 4, 1., &mut rand::rngs::StdRng::seed_from_u64(7));
+    let second = Matrix::<f32>::orthogonal(4, 4, 1., &mut rand::rngs::StdRng::seed_from_u64(7));
+    assert_eq!(first, second, "Testing the reproducibility with a seeded rng");
+    let first = Matrix::<f32>::xavier_normal(4, 4, &mut rand::rngs::StdRng::seed_from_u64(7));
+    assert_eq!(first, Matrix::<f32>::xavier_normal(4, 4, &mut rand::rngs::StdRng::seed_from_u64(7)), "Testing the reproducibility with a seeded rng");
+
+    assert_eq!(Matrix::<f64>::he_normal(0, 3, &mut rng).shape(), (0, 3), "Testing he_normal without inputs");
+    assert_eq!(Matrix::<f64>::xavier_uniform(0, 0, &mut rng).shape(), (0, 0), "Testing xavier_uniform on an empty shape");
+    assert_eq!(Matrix::<f32>::lecun_normal(4, 0, &mut rng).shape(), (4, 0), "Testing lecun_normal without outputs");
+    assert_eq!(Matrix::<f64>::orthogonal(0, 3, 1., &mut rng).shape(), (0, 3), "Testing orthogonal on an empty shape");
+}
+
+#[test]
+fn reductions() {
+    let matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![1., -5., 3.,
+                    4.,  2., 4.]
+    };
+    assert_eq!(matrix.clone().sum_col().data, vec![-1., 10.], "Testing sum_col on a non square matrix");
+    assert_eq!(matrix.sum(Axis::Rows), Matrix { nb_lines : 2, nb_columns : 1, data : vec![-1., 10.] }, "Testing the sum of each line");
+    assert_eq!(matrix.sum(Axis::Columns), matrix.clone().sum_line(), "Testing the sum of each column");
+    assert_eq!(matrix.sum(Axis::All).data, vec![9.], "Testing the sum of all the elements");
+    assert_eq!(matrix.mean(Axis::Columns).data, vec![2.5, -1.5, 3.5], "Testing the mean");
+    assert_eq!(matrix.prod(Axis::Rows).data, vec![-15., 32.], "Testing the product");
+    assert_eq!(matrix.min(Axis::Columns).data, vec![1., -5., 3.], "Testing the minimum");
+    assert_eq!(matrix.max(Axis::All).data, vec![4.], "Testing the maximum");
+    assert_eq!(matrix.argmax(Axis::Rows).data, vec![2, 0], "Testing argmax on each line, the first position is kept");
+    assert_eq!(matrix.argmin(Axis::Columns).data, vec![0, 0, 0], "Testing argmin on each column");
+    assert_eq!(matrix.argmin(Axis::All).data, vec![1], "Testing argmin on all the elements");
+    assert_eq!(matrix.norm(Axis::Rows, Norm::L1).data, vec![9., 10.], "Testing the L1 norm");
+    assert_eq!(matrix.norm(Axis::Columns, Norm::L2).data, vec![17f64.sqrt(), 29f64.sqrt(), 5.], "Testing the L2 norm");
+    assert_eq!(matrix.norm(Axis::All, Norm::Inf).data, vec![5.], "Testing the infinity norm");
+    assert_eq!(Matrix::<f64>::zeros(2, 0).try_max(Axis::Rows), Err(MatrixError::EmptyReduction((2, 0))), "Testing the maximum along an empty axis");
+    assert_eq!(Matrix::<f64>::zeros(2, 0).max(Axis::Columns).shape(), (1, 0), "Testing the maximum of each of the zero columns");
+
+    let mask = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![true, false,
+                    true, true]
+    };
+    assert_eq!(mask.any(Axis::Rows).data, vec![true, true], "Testing any");
+    assert_eq!(mask.all(Axis::Columns).data, vec![true, false], "Testing all");
+    assert_eq!(mask.all(Axis::All).data, vec![false], "Testing all on all the elements");
+}
+
+#[test]
+fn arithmetic_operators() {
+    let a = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![7, 8,
+                    9, 10]
+    };
+    let b = Matrix {
+        nb_lines : 2,
+        nb_columns : 2,
+        data : vec![1, 3,
+                    2, 4]
+    };
+    assert_eq!((&a - &b).data, vec![6, 5, 7, 6], "Testing subtraction with &Matrix and &Matrix");
+    assert_eq!(a.clone() - b.clone(), &a - &b, "Testing subtraction with Matrix and Matrix");
+    assert_eq!((&a / &b).data, vec![7, 2, 4, 2], "Testing the elementwise division");
+    assert_eq!((a.clone() % &b).data, vec![0, 2, 1, 2], "Testing the elementwise remainder");
+    assert_eq!(&a & b.clone(), Matrix { nb_lines : 2, nb_columns : 2, data : vec![7, 24, 18, 40] }, "Testing the Hadamard product with &Matrix and Matrix");
+    assert_eq!(2 * &a, &a * 2, "Testing the scalar on the left");
+    assert_eq!((0.5 * Matrix::<f64>::ones(1, 2)).data, vec![0.5, 0.5], "Testing the scalar on the left with floats");
+    assert_eq!((2.0 + Matrix::<f64>::ones(1, 2)).data, vec![3., 3.], "Testing the addition with a scalar on the left");
+    assert_eq!((1.0 - Matrix::<f64>::ones(1, 2) * 0.25).data, vec![0.75, 0.75], "Testing the subtraction from a scalar");
+    assert_eq!((1.0 / &(Matrix::<f64>::ones(1, 2) * 4.)).data, vec![0.25, 0.25], "Testing the division of a scalar");
+    assert_eq!((20i32 - &a).data, vec![13, 12, 11, 10], "Testing the subtraction from an integer");
+    assert_eq!((70i32 / &a).data, vec![10, 8, 7, 7], "Testing the division of an integer");
+    assert_eq!((10i32 % a.clone()).data, vec![3, 2, 1, 0], "Testing the remainder of an integer");
+    assert_eq!((&a % 4).data, vec![3, 0, 1, 2], "Testing the remainder by a scalar");
+    assert_eq!(a.try_sub(Matrix::zeros(1, 2)), Err(MatrixError::Subtraction((2, 2), (1, 2))), "Testing try_sub with different shapes");
+    assert_eq!(a.try_div(Matrix::ones(2, 1)), Err(MatrixError::Division((2, 2), (2, 1))), "Testing try_div with different shapes");
+
+    let mut c = a.clone();
+    c += &b;
+    c -= b.clone();
+    assert_eq!(c, a, "Testing += and -=");
+    c *= 3;
+    c /= 3;
+    c %= 5;
+    assert_eq!(c.data, vec![2, 3, 4, 0], "Testing *=, /= and %= with scalars");
+    c &= &b;
+    c /= b.clone();
+    c %= &Matrix::ones(2, 2);
+    assert_eq!(c, Matrix::zeros(2, 2), "Testing &=, /= and %= with matrices");
+    let mut d = Matrix::<i32>::ones(3, 2);
+    d *= &a;
+    assert_eq!(d.shape(), (3, 2), "Testing *= with a matrix");
+    assert_eq!(d[0], [16, 18], "Testing *= with a matrix");
+}
+
+#[test]
+#[should_panic(expected = "Can't add two matrices with different shape !!")]
+fn add_assign_panics_on_shape_mismatch() {
+    let mut matrix = Matrix::<i32>::zeros(3, 2);
+    matrix += Matrix::<i32>::zeros(2, 2);
+}
+
+#[test]
+fn broadcasting() {
+    let matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![1., 2., 3.,
+                    5., 6., 7.]
+    };
+    let means = matrix.mean(Axis::Columns);
+    assert_eq!(matrix.broadcast_sub(&means).data, vec![-2., -2., -2., 2., 2., 2.], "Testing the subtraction of the column means");
+    let weights = Matrix {
+        nb_lines : 2,
+        nb_columns : 1,
+        data : vec![2., 0.5]
+    };
+    assert_eq!(matrix.broadcast_mul(&weights).data, vec![2., 4., 6., 2.5, 3., 3.5], "Testing the product by per-sample weights");
+    assert_eq!(weights.broadcast_add(Matrix { nb_lines : 1, nb_columns : 2, data : vec![1., 2.] }).data, vec![3., 4., 1.5, 2.5], "Testing a column with a line");
+    assert_eq!(matrix.broadcast_div(Matrix::ones(1, 1) * 2.).data, vec![0.5, 1., 1.5, 2.5, 3., 3.5], "Testing the division by a 1 x 1 matrix");
+    assert_eq!(matrix.broadcast_min(weights.broadcast_mul(Matrix::ones(1, 1) * 2.)).data, vec![1., 2., 3., 1., 1., 1.], "Testing the minimum");
+    assert_eq!(matrix.broadcast_max(&means).data, vec![3., 4., 5., 5., 6., 7.], "Testing the maximum");
+    assert_eq!(matrix.broadcast_gt(&means).data, vec![false, false, false, true, true, true], "Testing the comparisons");
+    assert_eq!(matrix.broadcast_eq(Matrix::ones(1, 1) * 6.).data, vec![false, false, false, false, true, false], "Testing the equality");
+    assert_eq!(matrix.broadcast_with(&Matrix::<usize>::ones(1, 3), |a, b| *a as usize + b).data, vec![2, 3, 4, 6, 7, 8], "Testing broadcast_with");
+    assert_eq!(matrix.try_broadcast_add(Matrix::zeros(3, 1)), Err(MatrixError::Broadcasting((2, 3), (3, 1))), "Testing incompatible shapes");
+    assert_eq!(Matrix::<f64>::zeros(2, 0).try_broadcast_shape(&matrix.sum(Axis::All)), Ok((2, 0)), "Testing the broadcast with an empty matrix");
+}
+
+#[test]
+fn generic_maps_and_zips() {
+    let mut matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![1., -2., 3.,
+                    -4., 5., -6.],
+    };
+    assert_eq!(matrix.map_ref(|x| *x > 0.).data, vec![true, false, true, false, true, false], "Testing map_ref to a mask");
+    assert_eq!(matrix.map_indexed(|i, j, x| (i, j, *x > 0.)).data, vec![(0, 0, true), (0, 1, false), (0, 2, true), (1, 0, false), (1, 1, true), (1, 2, false)], "Testing map_indexed");
+    matrix.map_inplace(|x : &mut f64| *x = x.abs());
+    assert_eq!(matrix.data, vec![1., 2., 3., 4., 5., 6.], "Testing map_inplace");
+    let image = matrix.clone().map_to(|x| (x * 40.) as u8);
+    assert_eq!(image.shape(), (2, 3), "Testing the shape kept by map_to");
+    assert_eq!(image.data, vec![40, 80, 120, 160, 200, 240], "Testing map_to");
+    assert_eq!(matrix.zip_with(&image, |a, b| *a as u32 + *b as u32).data, vec![41, 82, 123, 164, 205, 246], "Testing zip_with");
+    let mask = matrix.map_ref(|x| *x > 3.);
+    assert_eq!(mask.zip3_with(&matrix, &image, |m, a, b| if *m { *a } else { *b as f64 }).data, vec![40., 80., 120., 4., 5., 6.], "Testing zip3_with");
+    assert_eq!(matrix.try_zip_with(&Matrix::<u8>::zeros(3, 2), |a, b| *a + *b as f64), Err(MatrixError::Zipping((2, 3), (3, 2))), "Testing zip_with on different shapes");
+    assert!(mask.try_zip3_with(&matrix, &Matrix::<f64>::zeros(2, 2), |_, a, b| a + b).is_err(), "Testing zip3_with on different shapes");
+}
+
+#[test]
+fn masks() {
+    let mut matrix = Matrix {
+        nb_lines : 2,
+        nb_columns : 3,
+        data : vec![-3., 0.5, f64::NAN,
+                    2., -0.5, 10.],
+    };
+    assert_eq!(matrix.gt(0.).data, vec![false, true, false, true, false, true], "Testing gt");
+    assert_eq!(matrix.ge(2.).data, vec![false, false, false, true, false, true], "Testing ge");
+    assert_eq!(matrix.lt(0.).data, vec![true, false, false, false, true, false], "Testing lt");
+    assert_eq!(matrix.le(0.5).data, vec![true, true, false, false, true, false], "Testing le");
+    assert_eq!(matrix.eq_elem(2.).data, vec![false, false, false, true, false, false], "Testing eq_elem");
+    let nan = matrix.is_nan();
+    assert_eq!(nan.data, vec![false, false, true, false, false, false], "Testing is_nan");
+    assert_eq!(nan.count_true(), 1, "Testing count_true");
+    matrix.masked_fill(&nan, 0.);
+    assert_eq!(matrix.data, vec![-3., 0.5, 0., 2., -0.5, 10.], "Testing masked_fill");
+    let relu_derivative = Matrix::<f64>::select(&matrix.gt(0.), &Matrix::ones(2, 3), &Matrix::zeros(2, 3));
+    assert_eq!(relu_derivative.data, vec![0., 1., 0., 1., 0., 1.], "Testing select");
+    let mut clipped = matrix.clone();
+    clipped.masked_assign(&matrix.gt(1.), &Matrix::ones(2, 3));
+    clipped.masked_fill(&matrix.lt(-1.), -1.);
+    assert_eq!(clipped.data, vec![-1., 0.5, 0., 1., -0.5, 1.], "Testing the gradient clipping with masks");
+    assert_eq!(matrix.try_masked_fill(&Matrix { nb_lines : 3, nb_columns : 2, data : vec![true; 6] }, 1.), Err(MatrixError::Mask((3, 2), (2, 3))), "Testing a mask of a different shape");
+    assert_eq!(Matrix::try_select(&nan, &matrix, &Matrix::zeros(2, 2)).unwrap_err(), MatrixError::Mask((2, 3), (2, 2)), "Testing select with different shapes");
+}
@@ -5,7 +5,7 @@ impl<T> PartialEq<Self> for Matrix<T> where T: PartialEq {
         if self.shape() != other.shape() {
             return false
         }
-        for (a, b) in self.into_iter().zip(other.into_iter()) {
+        for (a, b) in self.into_iter().zip(other) {
             if a != b {
                 return false
             }
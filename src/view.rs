@@ -0,0 +1,519 @@
+use std::ops::{Add, AddAssign, Bound, BitAnd, BitAndAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Range, RangeBounds, Rem, RemAssign, Sub, SubAssign};
+use num_traits::Zero;
+use crate::{Matrix, MatrixError};
+use crate::gemm::mul_into;
+use crate::operations::{ADDING_ERROR, DIVISION_ERROR, HADAMAR_PRODUCT_ERROR, REMAINDER_ERROR, SUBTRACTION_ERROR};
+
+
+/*
+Borrowed window over the data of a Matrix, the element (i, j) is data[offset + i * line_stride + j * column_stride]
+No data is copied when creating a view
+ */
+#[derive(Debug)]
+pub struct MatrixView<'a, T> {
+    data : &'a [T],
+    offset : usize,
+    nb_lines : usize,
+    nb_columns : usize,
+    line_stride : usize,
+    column_stride : usize,
+}
+
+/*
+Mutable borrowed window over the data of a Matrix, see MatrixView
+ */
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    data : &'a mut [T],
+    offset : usize,
+    nb_lines : usize,
+    nb_columns : usize,
+    line_stride : usize,
+    column_stride : usize,
+}
+
+fn resolve<R>(range : R, len : usize) -> Range<usize> where R : RangeBounds<usize> { //convert any range into begin..end, panic if out of bounds
+    let begin = match range.start_bound() {
+        Bound::Included(&begin) => begin,
+        Bound::Excluded(&begin) => begin + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(begin <= end && end <= len, "The range {}..{} is out of bounds for a dimension of length {}", begin, end, len);
+    begin..end
+}
+
+
+//METHODS TO CREATE VIEWS FROM A MATRIX
+impl<T> Matrix<T> {
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data : &self.data,
+            offset : 0,
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            line_stride : self.nb_columns,
+            column_stride : 1,
+        }
+    }
+
+    pub fn as_view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut {
+            offset : 0,
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            line_stride : self.nb_columns,
+            column_stride : 1,
+            data : &mut self.data,
+        }
+    }
+
+    pub fn view<R, C>(&self, lines : R, columns : C) -> MatrixView<'_, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        self.as_view().into_view(lines, columns)
+    }
+
+    pub fn view_mut<R, C>(&mut self, lines : R, columns : C) -> MatrixViewMut<'_, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        self.as_view_mut().into_view_mut(lines, columns)
+    }
+
+    pub fn row(&self, i : usize) -> MatrixView<'_, T> { //return the line i as a 1 x nb_columns view
+        self.view(i..=i, ..)
+    }
+
+    pub fn row_mut(&mut self, i : usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(i..=i, ..)
+    }
+
+    pub fn col(&self, j : usize) -> MatrixView<'_, T> { //return the column j as a nb_lines x 1 view
+        self.view(.., j..=j)
+    }
+
+    pub fn col_mut(&mut self, j : usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(.., j..=j)
+    }
+
+    pub fn t_view(&self) -> MatrixView<'_, T> { //return the transposed matrix without copying it
+        self.as_view().t()
+    }
+}
+
+impl<'a, T> From<&'a Matrix<T>> for MatrixView<'a, T> {
+    fn from(matrix : &'a Matrix<T>) -> Self {
+        matrix.as_view()
+    }
+}
+
+impl<'a, T> From<&'a mut Matrix<T>> for MatrixViewMut<'a, T> {
+    fn from(matrix : &'a mut Matrix<T>) -> Self {
+        matrix.as_view_mut()
+    }
+}
+
+impl<'a, T> From<MatrixView<'a, T>> for Matrix<T> where T : Copy {
+    fn from(view : MatrixView<'a, T>) -> Self {
+        view.to_matrix()
+    }
+}
+
+
+//IMPLEMENTATION OF THE CLONE AND COPY TRAITS
+impl<'a, T> Clone for MatrixView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T> Copy for MatrixView<'a, T> {}
+
+
+//GETTERS AND SUB-VIEWS
+impl<'a, T> MatrixView<'a, T> {
+    pub fn lines(&self) -> usize {
+        self.nb_lines
+    }
+
+    pub fn columns(&self) -> usize {
+        self.nb_columns
+    }
+
+    pub fn size(&self) -> usize {
+        self.nb_lines * self.nb_columns
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nb_lines, self.nb_columns)
+    }
+
+    pub fn strides(&self) -> (usize, usize) {
+        (self.line_stride, self.column_stride)
+    }
+
+    pub(crate) fn element(&self, i : usize, j : usize) -> &'a T { //no check is done on the dimensions
+        &self.data[self.offset + i * self.line_stride + j * self.column_stride]
+    }
+
+    pub fn get(&self, i : usize, j : usize) -> Option<&'a T> {
+        if i < self.nb_lines && j < self.nb_columns {
+            Some(&self.data[self.offset + i * self.line_stride + j * self.column_stride])
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn view<R, C>(&self, lines : R, columns : C) -> MatrixView<'a, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        self.into_view(lines, columns)
+    }
+
+    fn into_view<R, C>(self, lines : R, columns : C) -> MatrixView<'a, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        let lines = resolve(lines, self.nb_lines);
+        let columns = resolve(columns, self.nb_columns);
+        MatrixView {
+            data : self.data,
+            offset : self.offset + lines.start * self.line_stride + columns.start * self.column_stride,
+            nb_lines : lines.len(),
+            nb_columns : columns.len(),
+            line_stride : self.line_stride,
+            column_stride : self.column_stride,
+        }
+    }
+
+    pub fn row(&self, i : usize) -> MatrixView<'a, T> {
+        self.view(i..=i, ..)
+    }
+
+    pub fn col(&self, j : usize) -> MatrixView<'a, T> {
+        self.view(.., j..=j)
+    }
+
+    pub fn t(&self) -> MatrixView<'a, T> { //swap the dimensions and the strides
+        MatrixView {
+            data : self.data,
+            offset : self.offset,
+            nb_lines : self.nb_columns,
+            nb_columns : self.nb_lines,
+            line_stride : self.column_stride,
+            column_stride : self.line_stride,
+        }
+    }
+
+    pub fn iter(&self) -> MatrixViewIterator<'a, T> { //iterate over the elements line by line
+        MatrixViewIterator {
+            view : *self,
+            index : 0,
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> where T : Copy {
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.iter().copied().collect(),
+        }
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    pub fn lines(&self) -> usize {
+        self.nb_lines
+    }
+
+    pub fn columns(&self) -> usize {
+        self.nb_columns
+    }
+
+    pub fn size(&self) -> usize {
+        self.nb_lines * self.nb_columns
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nb_lines, self.nb_columns)
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data : &*self.data,
+            offset : self.offset,
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            line_stride : self.line_stride,
+            column_stride : self.column_stride,
+        }
+    }
+
+    pub fn view_mut<R, C>(&mut self, lines : R, columns : C) -> MatrixViewMut<'_, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        MatrixViewMut {
+            data : &mut *self.data,
+            offset : self.offset,
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            line_stride : self.line_stride,
+            column_stride : self.column_stride,
+        }.into_view_mut(lines, columns)
+    }
+
+    fn into_view_mut<R, C>(self, lines : R, columns : C) -> MatrixViewMut<'a, T> where R : RangeBounds<usize>, C : RangeBounds<usize> {
+        let lines = resolve(lines, self.nb_lines);
+        let columns = resolve(columns, self.nb_columns);
+        MatrixViewMut {
+            data : self.data,
+            offset : self.offset + lines.start * self.line_stride + columns.start * self.column_stride,
+            nb_lines : lines.len(),
+            nb_columns : columns.len(),
+            line_stride : self.line_stride,
+            column_stride : self.column_stride,
+        }
+    }
+
+    pub fn fill(&mut self, value : T) where T : Copy {
+        for i in 0..self.nb_lines {
+            for j in 0..self.nb_columns {
+                self[(i, j)] = value;
+            }
+        }
+    }
+
+    pub fn assign<'b, V>(&mut self, other : V) where T : Copy + 'b, V : Into<MatrixView<'b, T>> { //copy the values of other in the view, panic if the shapes differ
+        let other = other.into();
+        assert_eq!(self.shape(), other.shape(), "Can't assign a view with a different shape");
+        for i in 0..self.nb_lines {
+            for j in 0..self.nb_columns {
+                self[(i, j)] = other[(i, j)];
+            }
+        }
+    }
+
+    pub fn apply<F>(&mut self, f : F) where T : Copy, F : Fn(T) -> T { //apply a function to every element of the view in place
+        for i in 0..self.nb_lines {
+            for j in 0..self.nb_columns {
+                self[(i, j)] = f(self[(i, j)]);
+            }
+        }
+    }
+}
+
+
+//OVERLOADING [(., .)] OPERATOR
+impl<'a, T> Index<(usize, usize)> for MatrixView<'a, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.nb_lines && j < self.nb_columns, "The index ({}, {}) is out of bounds for a view of shape {:?}", i, j, self.shape());
+        &self.data[self.offset + i * self.line_stride + j * self.column_stride]
+    }
+}
+impl<'a, T> Index<(usize, usize)> for MatrixViewMut<'a, T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.nb_lines && j < self.nb_columns, "The index ({}, {}) is out of bounds for a view of shape {:?}", i, j, self.shape());
+        &self.data[self.offset + i * self.line_stride + j * self.column_stride]
+    }
+}
+impl<'a, T> IndexMut<(usize, usize)> for MatrixViewMut<'a, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.nb_lines && j < self.nb_columns, "The index ({}, {}) is out of bounds for a view of shape {:?}", i, j, self.shape());
+        &mut self.data[self.offset + i * self.line_stride + j * self.column_stride]
+    }
+}
+
+
+//ITERATOR OVER A VIEW
+pub struct MatrixViewIterator<'a, T> {
+    view : MatrixView<'a, T>,
+    index : usize,
+}
+impl<'a, T> Iterator for MatrixViewIterator<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.view.size() {
+            let res = self.view.get(self.index / self.view.nb_columns, self.index % self.view.nb_columns);
+            self.index += 1;
+            res
+        }
+        else {
+            None
+        }
+    }
+}
+impl<'a, T> IntoIterator for MatrixView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = MatrixViewIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+//FUNCTIONALITIES
+impl<'a, T> MatrixView<'a, T> where T : Copy {
+    pub fn map<F>(&self, f: F) -> Matrix<T> where F: Fn(T) -> T { //Apply a function to the view, the result is a new Matrix
+        Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.iter().map(|&a| f(a)).collect(),
+        }
+    }
+
+    pub fn sum(&self) -> T where T : Zero {
+        self.iter().fold(T::zero(), |sum, &a| sum + a)
+    }
+
+    pub fn sum_line(&self) -> Matrix<T> where T : AddAssign + Zero { //sum the lines together, the result is a line matrix
+        let mut res = Matrix::zeros(1, self.nb_columns);
+        for i in 0..self.nb_lines {
+            for j in 0..self.nb_columns {
+                res.data[j] += self[(i, j)];
+            }
+        }
+        res
+    }
+
+    pub fn sum_col(&self) -> Matrix<T> where T : AddAssign + Zero { //sum the columns together, the result is a column matrix
+        let mut res = Matrix::zeros(self.nb_lines, 1);
+        for i in 0..self.nb_lines {
+            for j in 0..self.nb_columns {
+                res.data[i] += self[(i, j)];
+            }
+        }
+        res
+    }
+
+    /*
+    Combine the elements at the same position in both views, error is used to build the MatrixError when the shapes are different
+     */
+    fn try_elementwise<'b, F>(&self, rhs : MatrixView<'b, T>, error : fn((usize, usize), (usize, usize)) -> MatrixError, f : F) -> Result<Matrix<T>, MatrixError> where F : Fn(T, T) -> T {
+        if self.shape() != rhs.shape() {
+            return Err(error(self.shape(), rhs.shape()))
+        }
+        Ok(Matrix {
+            nb_lines : self.nb_lines,
+            nb_columns : self.nb_columns,
+            data : self.iter().zip(rhs.iter()).map(|(&a, &b)| f(a, b)).collect(),
+        })
+    }
+
+    pub fn try_add<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Add<Output = T> + 'b, V : Into<MatrixView<'b, T>> {
+        self.try_elementwise(rhs.into(), MatrixError::Adding, T::add)
+    }
+
+    pub fn try_sub<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Sub<Output = T> + 'b, V : Into<MatrixView<'b, T>> {
+        self.try_elementwise(rhs.into(), MatrixError::Subtraction, T::sub)
+    }
+
+    pub fn try_hadamard<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + 'b, V : Into<MatrixView<'b, T>> {
+        self.try_elementwise(rhs.into(), MatrixError::HadamardProduct, T::mul)
+    }
+
+    pub fn try_div<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Div<Output = T> + 'b, V : Into<MatrixView<'b, T>> { //elementwise division
+        self.try_elementwise(rhs.into(), MatrixError::Division, T::div)
+    }
+
+    pub fn try_rem<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Rem<Output = T> + 'b, V : Into<MatrixView<'b, T>> { //elementwise remainder
+        self.try_elementwise(rhs.into(), MatrixError::Remainder, T::rem)
+    }
+
+    pub fn try_mul<'b, V>(&self, rhs : V) -> Result<Matrix<T>, MatrixError> where T : Mul<Output = T> + AddAssign + Zero + 'b, V : Into<MatrixView<'b, T>> {
+        let rhs = rhs.into();
+        let mut res = Matrix::new();
+        mul_into(*self, rhs, &mut res)?;
+        Ok(res)
+    }
+}
+
+
+/*
+Elementwise operator between views, and between a view and a borrowed Matrix, the result is a new Matrix
+ */
+macro_rules! view_operator {
+    ($trait:ident, $method:ident, $try_method:ident, $element_trait:ident) => {
+        impl<'a, 'b, T> $trait<MatrixView<'b, T>> for MatrixView<'a, T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
+            fn $method(self, rhs: MatrixView<'b, T>) -> Self::Output {
+                self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+            }
+        }
+        impl<'a, 'b, T> $trait<&'b Matrix<T>> for MatrixView<'a, T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
+            fn $method(self, rhs: &'b Matrix<T>) -> Self::Output {
+                self.$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+            }
+        }
+        impl<'b, T> $trait<MatrixView<'b, T>> for &Matrix<T> where T : $element_trait<Output = T> + Copy {
+            type Output = Matrix<T>;
+            fn $method(self, rhs: MatrixView<'b, T>) -> Self::Output {
+                self.as_view().$try_method(rhs).unwrap_or_else(|error| panic!("{}", error))
+            }
+        }
+    };
+}
+
+/*
+Compound assignment on a mutable view with a view or a borrowed Matrix of the same shape, panic with $message otherwise
+ */
+macro_rules! view_assign_operator {
+    ($trait:ident, $method:ident, $element_trait:ident, $op:tt, $message:expr) => {
+        impl<'a, 'b, T> $trait<MatrixView<'b, T>> for MatrixViewMut<'a, T> where T : $element_trait + Copy {
+            fn $method(&mut self, rhs: MatrixView<'b, T>) {
+                assert_eq!(self.shape(), rhs.shape(), "{}", $message);
+                for i in 0..self.nb_lines {
+                    for j in 0..self.nb_columns {
+                        self[(i, j)] $op rhs[(i, j)];
+                    }
+                }
+            }
+        }
+        impl<'a, 'b, T> $trait<&'b Matrix<T>> for MatrixViewMut<'a, T> where T : $element_trait + Copy {
+            fn $method(&mut self, rhs: &'b Matrix<T>) {
+                self.$method(rhs.as_view());
+            }
+        }
+    };
+}
+
+
+//OVERLOADING +, -, &, / AND % OPERATORS FOR VIEWS
+view_operator!(Add, add, try_add, Add);
+view_operator!(Sub, sub, try_sub, Sub);
+view_operator!(BitAnd, bitand, try_hadamard, Mul);
+view_operator!(Div, div, try_div, Div);
+view_operator!(Rem, rem, try_rem, Rem);
+
+impl<'a, T> Neg for MatrixView<'a, T> where T : Neg<Output = T> + Copy {
+    type Output = Matrix<T>;
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+
+//OVERLOADING * OPERATOR FOR VIEWS
+impl<'a, 'b, T> Mul<MatrixView<'b, T>> for MatrixView<'a, T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    type Output = Matrix<T>;
+    fn mul(self, rhs: MatrixView<'b, T>) -> Self::Output {
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+impl<'a, 'b, T> Mul<&'b Matrix<T>> for MatrixView<'a, T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    type Output = Matrix<T>;
+    fn mul(self, rhs: &'b Matrix<T>) -> Self::Output {
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+impl<'b, T> Mul<MatrixView<'b, T>> for &Matrix<T> where T : Mul<Output = T> + AddAssign + Zero + Copy {
+    type Output = Matrix<T>;
+    fn mul(self, rhs: MatrixView<'b, T>) -> Self::Output {
+        self.as_view().try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+
+//OVERLOADING +=, -=, &=, /= AND %= OPERATORS FOR MUTABLE VIEWS
+view_assign_operator!(AddAssign, add_assign, AddAssign, +=, ADDING_ERROR);
+view_assign_operator!(SubAssign, sub_assign, SubAssign, -=, SUBTRACTION_ERROR);
+view_assign_operator!(BitAndAssign, bitand_assign, MulAssign, *=, HADAMAR_PRODUCT_ERROR);
+view_assign_operator!(DivAssign, div_assign, DivAssign, /=, DIVISION_ERROR);
+view_assign_operator!(RemAssign, rem_assign, RemAssign, %=, REMAINDER_ERROR);